@@ -0,0 +1,81 @@
+/* Bindings to edlib/edlib/include/edlib.h as generated by bindgen 0.63 (without doc comments),
+ * used by the build instead of running bindgen when feature pregenerated-bindings is enabled.
+ * Regenerate when edlib is updated with :
+ *     bindgen edlib/edlib/include/edlib.h -o bindings/bindings.rs --no-doc-comments
+ */
+
+pub const EDLIB_STATUS_OK: u32 = 0;
+pub const EDLIB_STATUS_ERROR: u32 = 1;
+pub const EDLIB_EDOP_MATCH: u32 = 0;
+pub const EDLIB_EDOP_INSERT: u32 = 1;
+pub const EDLIB_EDOP_DELETE: u32 = 2;
+pub const EDLIB_EDOP_MISMATCH: u32 = 3;
+pub const EdlibAlignMode_EDLIB_MODE_NW: EdlibAlignMode = 0;
+pub const EdlibAlignMode_EDLIB_MODE_SHW: EdlibAlignMode = 1;
+pub const EdlibAlignMode_EDLIB_MODE_HW: EdlibAlignMode = 2;
+pub type EdlibAlignMode = ::std::os::raw::c_uint;
+pub const EdlibAlignTask_EDLIB_TASK_DISTANCE: EdlibAlignTask = 0;
+pub const EdlibAlignTask_EDLIB_TASK_LOC: EdlibAlignTask = 1;
+pub const EdlibAlignTask_EDLIB_TASK_PATH: EdlibAlignTask = 2;
+pub type EdlibAlignTask = ::std::os::raw::c_uint;
+pub const EdlibCigarFormat_EDLIB_CIGAR_STANDARD: EdlibCigarFormat = 0;
+pub const EdlibCigarFormat_EDLIB_CIGAR_EXTENDED: EdlibCigarFormat = 1;
+pub type EdlibCigarFormat = ::std::os::raw::c_uint;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct EdlibEqualityPair {
+    pub first: ::std::os::raw::c_char,
+    pub second: ::std::os::raw::c_char,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct EdlibAlignConfig {
+    pub k: ::std::os::raw::c_int,
+    pub mode: EdlibAlignMode,
+    pub task: EdlibAlignTask,
+    pub additionalEqualities: *const EdlibEqualityPair,
+    pub additionalEqualitiesLength: ::std::os::raw::c_int,
+}
+extern "C" {
+    pub fn edlibNewAlignConfig(
+        k: ::std::os::raw::c_int,
+        mode: EdlibAlignMode,
+        task: EdlibAlignTask,
+        additionalEqualities: *const EdlibEqualityPair,
+        additionalEqualitiesLength: ::std::os::raw::c_int,
+    ) -> EdlibAlignConfig;
+}
+extern "C" {
+    pub fn edlibDefaultAlignConfig() -> EdlibAlignConfig;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct EdlibAlignResult {
+    pub status: ::std::os::raw::c_int,
+    pub editDistance: ::std::os::raw::c_int,
+    pub endLocations: *mut ::std::os::raw::c_int,
+    pub startLocations: *mut ::std::os::raw::c_int,
+    pub numLocations: ::std::os::raw::c_int,
+    pub alignment: *mut ::std::os::raw::c_uchar,
+    pub alignmentLength: ::std::os::raw::c_int,
+    pub alphabetLength: ::std::os::raw::c_int,
+}
+extern "C" {
+    pub fn edlibFreeAlignResult(result: EdlibAlignResult);
+}
+extern "C" {
+    pub fn edlibAlign(
+        query: *const ::std::os::raw::c_char,
+        queryLength: ::std::os::raw::c_int,
+        target: *const ::std::os::raw::c_char,
+        targetLength: ::std::os::raw::c_int,
+        config: EdlibAlignConfig,
+    ) -> EdlibAlignResult;
+}
+extern "C" {
+    pub fn edlibAlignmentToCigar(
+        alignment: *const ::std::os::raw::c_uchar,
+        alignmentLength: ::std::os::raw::c_int,
+        cigarFormat: EdlibCigarFormat,
+    ) -> *mut ::std::os::raw::c_char;
+}
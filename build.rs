@@ -1,13 +1,42 @@
 fn main() {
     // Cmake edlib and link it.
-    let out_dir = cmake::Config::new("edlib")
-        .cflag("-D CMAKE_BUILD_TYPE=Release")
-        .build();
+    let mut cmake_config = cmake::Config::new("edlib");
+    cmake_config.cflag("-D CMAKE_BUILD_TYPE=Release");
+    // With the static feature, force the static archive libedlib.a so binaries have no runtime
+    // dependency on libedlib.so. edlib cmake builds a static library by default, but the link kind
+    // was left to the linker which could pick a shared library found in the search path.
+    #[cfg(feature = "static")]
+    cmake_config.define("BUILD_SHARED_LIBS", "OFF");
+    let out_dir = cmake_config.build();
     println!("cargo:rustc-link-search=native={}/lib", out_dir.display());
+    #[cfg(feature = "static")]
+    println!("cargo:rustc-link-lib=static=edlib");
+    #[cfg(not(feature = "static"))]
     println!("cargo:rustc-link-lib=edlib");
 
-    // Link the standard library.
-    if cfg!(target_os = "macos") {
+    // Expose the edlib version declared in its cmake project to the crate.
+    let cmake_path = "edlib/CMakeLists.txt";
+    println!("cargo:rerun-if-changed={}", cmake_path);
+    let cmake_lists =
+        std::fs::read_to_string(cmake_path).expect("Unable to read edlib CMakeLists.txt");
+    let version = cmake_lists
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("project(edlib VERSION "))
+        .filter_map(|rest| rest.split(|c: char| c == ')' || c.is_whitespace()).next())
+        .next()
+        .expect("No edlib version in CMakeLists.txt");
+    println!("cargo:rustc-env=EDLIB_VERSION={}", version);
+
+    // Link the C++ standard library of the target (not the host, so cfg! cannot be used here).
+    // It is needed by the edlib archive whatever its link kind, and stays a dynamic system library.
+    // libc++ on Apple and BSD targets, nothing on MSVC which links its runtime itself
+    // and on wasm where the toolchain provides it, libstdc++ otherwise.
+    let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    let target_env = std::env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default();
+    let target_arch = std::env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+    if target_arch == "wasm32" || target_env == "msvc" {
+        // C++ runtime provided by the toolchain.
+    } else if ["macos", "ios", "freebsd", "openbsd"].contains(&target_os.as_str()) {
         println!("cargo:rustc-link-lib=c++");
     } else {
         println!("cargo:rustc-link-lib=stdc++");
@@ -18,6 +47,7 @@ fn main() {
     println!("cargo:rerun-if-changed={}", impl_path);
     let header_path = "edlib/edlib/include/edlib.h";
     println!("cargo:rerun-if-changed={}", header_path);
+    #[cfg(not(feature = "pregenerated-bindings"))]
     bindgen::Builder::default()
         .header(header_path)
         .parse_callbacks(Box::new(bindgen::CargoCallbacks))
@@ -25,4 +55,13 @@ fn main() {
         .expect("Unable to generate bindings")
         .write_to_file(out_dir.join("bindings.rs"))
         .expect("Couldn't write bindings!");
+
+    // Or use the checked-in bindings, so libclang is not needed.
+    #[cfg(feature = "pregenerated-bindings")]
+    {
+        let bindings_path = "bindings/bindings.rs";
+        println!("cargo:rerun-if-changed={}", bindings_path);
+        std::fs::copy(bindings_path, out_dir.join("bindings.rs"))
+            .expect("Couldn't copy pregenerated bindings!");
+    }
 }
@@ -0,0 +1,464 @@
+//! Functions running many alignments at once.
+//! With the rayon feature, alignments are distributed over threads where noted.
+
+use crate::*;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Finds among targets the one with smallest edit distance to query, in given mode.
+/// Returns index in targets and edit distance of best target (the first one in case of ties),
+/// None if targets is empty.
+///
+/// After a candidate at distance d is found, next alignments are run with k = d - 1
+/// so edlib can stop early on targets that cannot do better. This is much faster than running
+/// independent full alignments when most targets are far from query.
+///
+/// With max_k = Some(k), only targets at distance at most k are accepted : k is used as the bound
+/// of the first alignments instead of running them unbounded, then tightened as above once a candidate is found.
+/// None is returned if no target is within k (or if k is negative).
+/// With max_k = None the first alignment is unbounded, so a best target is always found if targets is not empty.
+#[allow(non_snake_case)]
+pub fn edlibBestMatchRs(
+    query: &[u8],
+    targets: &[&[u8]],
+    mode: EdlibAlignModeRs,
+    max_k: Option<i32>,
+) -> Option<(usize, u32)> {
+    let mut config = EdlibAlignConfigRs {
+        mode,
+        ..Default::default()
+    };
+    if let Some(k) = max_k {
+        if k < 0 {
+            return None;
+        }
+        config.k = k;
+    }
+    let mut best: Option<(usize, u32)> = None;
+    for (i, target) in targets.iter().enumerate() {
+        let align_res = edlibAlignRs(query, target, &config);
+        if align_res.status != EDLIB_STATUS_OK || align_res.editDistance < 0 {
+            // distance larger than current best
+            continue;
+        }
+        let distance = align_res.editDistance as u32;
+        best = Some((i, distance));
+        if distance == 0 {
+            break;
+        }
+        config.k = distance as i32 - 1;
+    }
+    best
+} // end of edlibBestMatchRs
+
+/// Computes the matrix of edit distances between all pairs of seqs in given mode,
+/// entry \[i\]\[j\] being the distance of seqs\[i\] as query against seqs\[j\] as target.
+/// The diagonal is 0 without calling edlib.
+/// In mode EDLIB_MODE_NW distance is symmetric so only the upper triangle is computed and mirrored.
+/// In SHW and HW modes distance is not symmetric and all entries are computed.
+/// With the rayon feature rows are computed in parallel.
+#[allow(non_snake_case)]
+pub fn edlibDistanceMatrixRs(seqs: &[&[u8]], mode: EdlibAlignModeRs) -> Vec<Vec<i32>> {
+    edlibDistanceMatrixWithProgress(seqs, mode, |_, _| {})
+} // end of edlibDistanceMatrixRs
+
+/// Same as edlibDistanceMatrixRs, calling progress(completed_pairs, total_pairs) after each pair is aligned.
+/// total_pairs is the number of alignments run : n * (n - 1) / 2 in mode EDLIB_MODE_NW, n * (n - 1) otherwise.
+/// Completed pairs are counted with an atomic counter, so each call gets a distinct count from 1 to total_pairs.
+/// With the rayon feature progress is called concurrently from several threads (hence Sync), and calls
+/// can be seen out of order : the call with completed_pairs == total_pairs is not necessarily the last one.
+#[allow(non_snake_case)]
+pub fn edlibDistanceMatrixWithProgress(
+    seqs: &[&[u8]],
+    mode: EdlibAlignModeRs,
+    progress: impl Fn(usize, usize) + Sync,
+) -> Vec<Vec<i32>> {
+    let n = seqs.len();
+    let symmetric = matches!(mode, EdlibAlignModeRs::EDLIB_MODE_NW);
+    let total = if symmetric {
+        n * n.saturating_sub(1) / 2
+    } else {
+        n * n.saturating_sub(1)
+    };
+    let completed = AtomicUsize::new(0);
+    let config = EdlibAlignConfigRs {
+        mode,
+        ..Default::default()
+    };
+    let compute_row = |i: usize| -> Vec<i32> {
+        (0..n)
+            .map(|j| {
+                if i == j || (symmetric && j < i) {
+                    0
+                } else {
+                    let distance = edlibAlignRs(seqs[i], seqs[j], &config).editDistance;
+                    progress(completed.fetch_add(1, Ordering::Relaxed) + 1, total);
+                    distance
+                }
+            })
+            .collect()
+    };
+    #[cfg(feature = "rayon")]
+    let mut matrix: Vec<Vec<i32>> = (0..n).into_par_iter().map(compute_row).collect();
+    #[cfg(not(feature = "rayon"))]
+    let mut matrix: Vec<Vec<i32>> = (0..n).map(compute_row).collect();
+    if symmetric {
+        for i in 1..n {
+            let (upper, lower) = matrix.split_at_mut(i);
+            for (j, row) in upper.iter().enumerate() {
+                lower[0][j] = row[i];
+            }
+        }
+    }
+    matrix
+} // end of edlibDistanceMatrixWithProgress
+
+/// Aligns each query against target and returns for each query Some(distance) if distance is at most k,
+/// None otherwise.
+/// Alignments run with task EDLIB_TASK_DISTANCE and given k so edlib stops early on hopeless queries,
+/// and locations are not copied from edlib. This is the filtering primitive to screen many reads.
+/// With the rayon feature queries are aligned in parallel.
+#[allow(non_snake_case)]
+pub fn edlibAlignBatchWithinK(
+    queries: &[&[u8]],
+    target: &[u8],
+    k: i32,
+    mode: EdlibAlignModeRs,
+) -> Vec<Option<u32>> {
+    let config = EdlibAlignConfigRs::new(k, mode, EdlibAlignTaskRs::EDLIB_TASK_DISTANCE, &[]);
+    let within = |query: &&[u8]| -> Option<u32> {
+        let (status, distance) = alignDistanceOnly(query, target, &config);
+        if status == EDLIB_STATUS_OK && distance >= 0 && (k < 0 || distance <= k) {
+            Some(distance as u32)
+        } else {
+            None
+        }
+    };
+    #[cfg(feature = "rayon")]
+    let res = queries.par_iter().map(within).collect();
+    #[cfg(not(feature = "rayon"))]
+    let res = queries.iter().map(within).collect();
+    res
+} // end of edlibAlignBatchWithinK
+
+/// Aligns each query of queries against target with config, and returns results in queries order.
+/// queries can be any iterable of owned or borrowed sequences (Vec<Vec<u8>>, &[&str], an iterator over records...),
+/// there is no need to collect references to a Vec<&[u8]> first.
+/// Alignments run sequentially as the iterator is consumed, the rayon feature is not used.
+#[allow(non_snake_case)]
+pub fn edlibAlignBatchIter<I, S>(
+    queries: I,
+    target: &[u8],
+    config: &EdlibAlignConfigRs,
+) -> Vec<EdlibAlignResultRs>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<[u8]>,
+{
+    queries
+        .into_iter()
+        .map(|query| edlibAlignRs(query.as_ref(), target, config))
+        .collect()
+} // end of edlibAlignBatchIter
+
+/// Concatenates queries with the byte sep between two consecutive queries, returning the packed sequence
+/// and the range of each query in it. The packed sequence is aligned once (as target, in HW mode) instead of
+/// aligning each short sequence separately, saving the per call overhead, and hit positions
+/// (locations in the packed sequence) are mapped back to a query index with demux_hit.
+/// sep must not appear in any query, so that no hit spans two queries with a match on sep
+/// (a hit can still span sep with a mismatch or a gap, check both its start and end).
+pub fn pack_queries(queries: &[&[u8]], sep: u8) -> (Vec<u8>, Vec<std::ops::Range<usize>>) {
+    let total: usize = queries.iter().map(|q| q.len() + 1).sum();
+    let mut packed = Vec::<u8>::with_capacity(total);
+    let mut ranges = Vec::with_capacity(queries.len());
+    for (i, query) in queries.iter().enumerate() {
+        if i > 0 {
+            packed.push(sep);
+        }
+        let start = packed.len();
+        packed.extend_from_slice(query);
+        ranges.push(start..packed.len());
+    }
+    (packed, ranges)
+} // end of pack_queries
+
+/// Returns the index of the range containing pos, ranges being those returned by pack_queries,
+/// or None if pos is on a separator or past the packed sequence.
+pub fn demux_hit(ranges: &[std::ops::Range<usize>], pos: usize) -> Option<usize> {
+    // ranges are sorted, find the last one starting at or before pos
+    let idx = ranges.partition_point(|r| r.start <= pos).checked_sub(1)?;
+    if ranges[idx].contains(&pos) {
+        Some(idx)
+    } else {
+        None
+    }
+} // end of demux_hit
+
+// reads a line without its line terminator (\n or \r\n) into buf, returns false at end of input
+pub(crate) fn read_line_bytes<R: std::io::BufRead>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+) -> std::io::Result<bool> {
+    buf.clear();
+    if reader.read_until(b'\n', buf)? == 0 {
+        return Ok(false);
+    }
+    if buf.last() == Some(&b'\n') {
+        buf.pop();
+        if buf.last() == Some(&b'\r') {
+            buf.pop();
+        }
+    }
+    Ok(true)
+}
+
+/// Reads a and b line by line in lockstep and returns the NW edit distance between each pair of lines
+/// with the same index. Line terminators (\n or \r\n) are not compared, and lines are compared as bytes
+/// so they need not be valid UTF-8.
+/// When an input has fewer lines, its missing lines are taken as empty : each unmatched trailing line
+/// counts its full length. The result has as many entries as the longer input has lines.
+pub fn file_line_distances<A: std::io::BufRead, B: std::io::BufRead>(
+    mut a: A,
+    mut b: B,
+) -> std::io::Result<Vec<u32>> {
+    let config = EdlibAlignConfigRs::default();
+    let mut line_a = Vec::<u8>::new();
+    let mut line_b = Vec::<u8>::new();
+    let mut distances = Vec::<u32>::new();
+    loop {
+        let more_a = read_line_bytes(&mut a, &mut line_a)?;
+        let more_b = read_line_bytes(&mut b, &mut line_b)?;
+        if !more_a && !more_b {
+            break;
+        }
+        let distance = edlibAlignRs(&line_a, &line_b, &config).editDistance;
+        distances.push(distance as u32);
+    }
+    Ok(distances)
+} // end of file_line_distances
+
+// Set of the k-mers of seq, empty if seq is shorter than k. k must not be 0.
+fn kmer_set(seq: &[u8], k: usize) -> HashSet<&[u8]> {
+    seq.windows(k).collect()
+}
+
+// true if target has a k-mer of the set. k must not be 0.
+fn has_kmer_in(kmers: &HashSet<&[u8]>, target: &[u8], k: usize) -> bool {
+    target.windows(k).any(|kmer| kmers.contains(kmer))
+}
+
+/// Returns true if query and target have at least one k-mer (substring of length k) in common.
+/// A sequence shorter than k has no k-mer so the result is then false. With k = 0 the result is true.
+pub fn shares_kmer(query: &[u8], target: &[u8], k: usize) -> bool {
+    if k == 0 {
+        return true;
+    }
+    has_kmer_in(&kmer_set(query, k), target, k)
+}
+
+/// Aligns query with config against the targets sharing at least one k-mer with query (see shares_kmer),
+/// and returns None for the others without calling edlib.
+/// The k-mers of query are put once in a HashSet so each target costs one pass over its k-mers.
+/// A skipped target is at distance at least query.len() / k (rounded down), as each edit can destroy
+/// at most one of the disjoint k-mers of query. Choose k small enough for the distances searched.
+#[allow(non_snake_case)]
+pub fn edlibAlignPrefiltered(
+    query: &[u8],
+    targets: &[&[u8]],
+    k: usize,
+    config: &EdlibAlignConfigRs,
+) -> Vec<Option<EdlibAlignResultRs>> {
+    let kmers = if k > 0 {
+        kmer_set(query, k)
+    } else {
+        HashSet::new()
+    };
+    targets
+        .iter()
+        .map(|target| {
+            if k == 0 || has_kmer_in(&kmers, target, k) {
+                Some(edlibAlignRs(query, target, config))
+            } else {
+                None
+            }
+        })
+        .collect()
+} // end of edlibAlignPrefiltered
+
+//===================================================================
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_best_match() {
+        let query = b"ACCTCTG";
+        let targets: Vec<&[u8]> = vec![b"TTTTTTTTTT", b"ACTCTGAAA", b"ACCTCTGA", b"GGGACCTTG"];
+        let best = edlibBestMatchRs(query, &targets, EdlibAlignModeRs::EDLIB_MODE_NW, None);
+        assert_eq!(best, Some((2, 1)));
+        let best = edlibBestMatchRs(query, &targets, EdlibAlignModeRs::EDLIB_MODE_HW, None);
+        assert_eq!(best, Some((2, 0)));
+        let best = edlibBestMatchRs(query, &[], EdlibAlignModeRs::EDLIB_MODE_NW, None);
+        assert!(best.is_none());
+    } // end of test_best_match
+
+    #[test]
+    fn test_best_match_max_k() {
+        let query = b"ACCTCTG";
+        let targets: Vec<&[u8]> = vec![b"TTTTTTTTTT", b"ACTCTGAAA", b"ACCTCTGA", b"GGGACCTTG"];
+        // best NW distance is 1
+        let best = edlibBestMatchRs(query, &targets, EdlibAlignModeRs::EDLIB_MODE_NW, Some(0));
+        assert!(best.is_none());
+        let best = edlibBestMatchRs(query, &targets, EdlibAlignModeRs::EDLIB_MODE_NW, Some(1));
+        assert_eq!(best, Some((2, 1)));
+        let best = edlibBestMatchRs(query, &targets, EdlibAlignModeRs::EDLIB_MODE_NW, Some(100));
+        assert_eq!(best, Some((2, 1)));
+        let best = edlibBestMatchRs(query, &targets, EdlibAlignModeRs::EDLIB_MODE_NW, Some(-1));
+        assert!(best.is_none());
+    } // end of test_best_match_max_k
+
+    #[test]
+    fn test_distance_matrix() {
+        let seqs: Vec<&[u8]> = vec![b"ACCTCTG", b"ACTCTGAAA", b"ACCTCTGA"];
+        let matrix = edlibDistanceMatrixRs(&seqs, EdlibAlignModeRs::EDLIB_MODE_NW);
+        assert_eq!(matrix, vec![vec![0, 4, 1], vec![4, 0, 3], vec![1, 3, 0]]);
+        for (i, row) in matrix.iter().enumerate() {
+            assert_eq!(row[i], 0);
+            for (j, &distance) in row.iter().enumerate() {
+                assert_eq!(distance, matrix[j][i]);
+            }
+        }
+        // infix mode is not symmetric
+        let matrix = edlibDistanceMatrixRs(&seqs, EdlibAlignModeRs::EDLIB_MODE_HW);
+        assert_eq!(matrix[0][2], 0);
+        assert_eq!(matrix[2][0], 1);
+        assert!(edlibDistanceMatrixRs(&[], EdlibAlignModeRs::EDLIB_MODE_NW).is_empty());
+    } // end of test_distance_matrix
+
+    #[test]
+    fn test_distance_matrix_progress() {
+        let seqs: Vec<&[u8]> = vec![b"ACCTCTG", b"ACTCTGAAA", b"ACCTCTGA", b"TTTT"];
+        for &(mode, total) in [
+            (EdlibAlignModeRs::EDLIB_MODE_NW, 6),
+            (EdlibAlignModeRs::EDLIB_MODE_HW, 12),
+        ]
+        .iter()
+        {
+            let calls = AtomicUsize::new(0);
+            let max_completed = AtomicUsize::new(0);
+            let matrix = edlibDistanceMatrixWithProgress(&seqs, mode, |completed, total_pairs| {
+                assert_eq!(total_pairs, total);
+                calls.fetch_add(1, Ordering::Relaxed);
+                max_completed.fetch_max(completed, Ordering::Relaxed);
+            });
+            assert_eq!(matrix, edlibDistanceMatrixRs(&seqs, mode));
+            assert_eq!(calls.into_inner(), total);
+            assert_eq!(max_completed.into_inner(), total);
+        }
+    } // end of test_distance_matrix_progress
+
+    #[test]
+    fn test_batch_within_k() {
+        let target = b"ACTCTGAAA";
+        let queries: Vec<&[u8]> = vec![b"ACCTCTG", b"ACTCTGAAA", b"ACTCTGAA", b"TTTT"];
+        let mode = EdlibAlignModeRs::EDLIB_MODE_NW;
+        // distances are 4, 0, 1 and 7
+        let res = edlibAlignBatchWithinK(&queries, target, 4, mode);
+        assert_eq!(res, vec![Some(4), Some(0), Some(1), None]);
+        let res = edlibAlignBatchWithinK(&queries, target, 3, mode);
+        assert_eq!(res, vec![None, Some(0), Some(1), None]);
+        let res = edlibAlignBatchWithinK(&queries, target, 0, mode);
+        assert_eq!(res, vec![None, Some(0), None, None]);
+        assert!(edlibAlignBatchWithinK(&[], target, 3, mode).is_empty());
+    } // end of test_batch_within_k
+
+    #[test]
+    fn test_prefiltered() {
+        let query = b"ACCTCTG";
+        assert!(shares_kmer(query, b"TTTTCTCTTT", 4));
+        assert!(!shares_kmer(query, b"TTTTCTCTTT", 5));
+        assert!(!shares_kmer(query, b"CTC", 4));
+        assert!(shares_kmer(query, b"", 0));
+        //
+        let targets: Vec<&[u8]> = vec![b"GGGGGGGGGG", b"TTTACCTCAGTTT", b"AAAAAAA"];
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        let res = edlibAlignPrefiltered(query, &targets, 4, &config);
+        assert_eq!(res.len(), 3);
+        assert!(res[0].is_none());
+        assert_eq!(res[1].as_ref().unwrap().editDistance, 1);
+        assert!(res[2].is_none());
+        let res = edlibAlignPrefiltered(query, &targets, 0, &config);
+        assert!(res.iter().all(|r| r.is_some()));
+    } // end of test_prefiltered
+
+    #[test]
+    fn test_align_batch_iter() {
+        let target = b"TTTTTTTTTTTTTTTTTTTTTACTCTGAAA";
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        let owned: Vec<Vec<u8>> = vec![b"ACTCTG".to_vec(), b"GGGG".to_vec(), b"ACCTCTG".to_vec()];
+        let res = edlibAlignBatchIter(&owned, target, &config);
+        let distances: Vec<i32> = res.iter().map(|r| r.editDistance).collect();
+        assert_eq!(distances, vec![0, 3, 1]);
+        let strs: Vec<&str> = vec!["ACCTCTG", "ACTCTG"];
+        let res = edlibAlignBatchIter(strs, target, &config);
+        let distances: Vec<i32> = res.iter().map(|r| r.editDistance).collect();
+        assert_eq!(distances, vec![1, 0]);
+        let res = edlibAlignBatchIter(Vec::<Vec<u8>>::new(), target, &config);
+        assert!(res.is_empty());
+    } // end of test_align_batch_iter
+
+    #[test]
+    fn test_pack_queries() {
+        let queries: Vec<&[u8]> = vec![b"ACGT", b"GG", b"TTTA"];
+        let (packed, ranges) = pack_queries(&queries, b'#');
+        assert_eq!(packed, b"ACGT#GG#TTTA".to_vec());
+        assert_eq!(ranges, vec![0..4, 5..7, 8..12]);
+        for (i, range) in ranges.iter().enumerate() {
+            assert_eq!(&packed[range.clone()], queries[i]);
+            for pos in range.clone() {
+                assert_eq!(demux_hit(&ranges, pos), Some(i));
+            }
+        }
+        // separators and out of range
+        assert_eq!(demux_hit(&ranges, 4), None);
+        assert_eq!(demux_hit(&ranges, 7), None);
+        assert_eq!(demux_hit(&ranges, 12), None);
+        // a hit found by aligning against the packed sequence
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(b"TTA", &packed, &config);
+        assert_eq!(align_res.editDistance, 0);
+        let end = align_res.end_locations().unwrap()[0] as usize;
+        let start = align_res.start_locations().unwrap()[0] as usize;
+        assert_eq!(demux_hit(&ranges, start), Some(2));
+        assert_eq!(demux_hit(&ranges, end), Some(2));
+        // empty queries give empty ranges that never match
+        let (packed, ranges) = pack_queries(&[b"", b"A"], b'#');
+        assert_eq!(packed, b"#A".to_vec());
+        assert_eq!(demux_hit(&ranges, 0), None);
+        assert_eq!(demux_hit(&ranges, 1), Some(1));
+    } // end of test_pack_queries
+
+    #[test]
+    fn test_file_line_distances() {
+        let a = "first line\nsecond line\nthird line\n";
+        let b = "first line\r\nsecond lane!\nthird line\n";
+        let distances = file_line_distances(a.as_bytes(), b.as_bytes()).unwrap();
+        assert_eq!(distances, vec![0, 2, 0]);
+        // b has an extra line, and no final newline
+        let b = "first line\nsecond line\nthird line\nfourth";
+        let distances = file_line_distances(a.as_bytes(), b.as_bytes()).unwrap();
+        assert_eq!(distances, vec![0, 0, 0, 6]);
+        let distances = file_line_distances(b.as_bytes(), a.as_bytes()).unwrap();
+        assert_eq!(distances, vec![0, 0, 0, 6]);
+        let distances = file_line_distances(&b""[..], &b""[..]).unwrap();
+        assert!(distances.is_empty());
+    } // end of test_file_line_distances
+} // mod tests
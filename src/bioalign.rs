@@ -0,0 +1,98 @@
+//! Conversion of edlib results to rust-bio alignment types (feature bio-types).
+//! The bio crate re-exports these types as bio::alignment::{Alignment, AlignmentOperation}.
+
+use crate::*;
+use bio_types::alignment::{Alignment, AlignmentMode, AlignmentOperation};
+
+/// Converts an edlib alignment of query against target to a bio Alignment, query being x and target y.
+/// Opcodes are mapped as match -> Match, mismatch -> Subst, insertion -> Ins, deletion -> Del.
+/// xstart is 0 and xend query.len() as edlib aligns the whole query, ystart and yend (exclusive)
+/// come from startLocations\[0\] and endLocations\[0\].
+/// score is set to the edit distance, so lower is better contrary to usual bio scores.
+/// The mode used is not stored in result, so it is set to Global if the alignment spans the whole target
+/// and to Semiglobal otherwise.
+///
+/// Returns None if result has no alignment path or the path contains an invalid opcode.
+pub fn to_bio_alignment(
+    query: &[u8],
+    target: &[u8],
+    result: &EdlibAlignResultRs,
+) -> Option<Alignment> {
+    let alignment = result.alignment()?;
+    let start = *result.start_locations()?.first()?;
+    let end = *result.end_locations()?.first()?;
+    // end is -1 when no target residue is aligned (empty target)
+    if start < 0 || end < -1 {
+        return None;
+    }
+    let ystart = start as usize;
+    let yend = (end + 1) as usize;
+    let mut operations = Vec::<AlignmentOperation>::with_capacity(alignment.len());
+    for &op in alignment {
+        let operation = match op {
+            0 => AlignmentOperation::Match,
+            1 => AlignmentOperation::Ins,
+            2 => AlignmentOperation::Del,
+            3 => AlignmentOperation::Subst,
+            _ => return None,
+        };
+        operations.push(operation);
+    }
+    let mode = if ystart == 0 && yend == target.len() {
+        AlignmentMode::Global
+    } else {
+        AlignmentMode::Semiglobal
+    };
+    Some(Alignment {
+        score: result.editDistance,
+        ystart,
+        xstart: 0,
+        yend,
+        xend: query.len(),
+        ylen: target.len(),
+        xlen: query.len(),
+        operations,
+        mode,
+    })
+} // end of to_bio_alignment
+
+//===================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_bio_alignment() {
+        let query = b"ACGGT";
+        let target = b"TTACTTGTTT";
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query, target, &config);
+        let bio_align = to_bio_alignment(query, target, &align_res).unwrap();
+        // AC matched at target 2, GG inserted, T matched at target 4
+        let expected = vec![
+            AlignmentOperation::Match,
+            AlignmentOperation::Match,
+            AlignmentOperation::Ins,
+            AlignmentOperation::Ins,
+            AlignmentOperation::Match,
+        ];
+        assert_eq!(bio_align.operations, expected);
+        assert_eq!(bio_align.score, align_res.editDistance);
+        assert_eq!(bio_align.ystart, 2);
+        assert_eq!(bio_align.yend, 5);
+        assert_eq!(bio_align.xend, query.len());
+        assert_eq!(bio_align.mode, AlignmentMode::Semiglobal);
+        // empty target : end location is -1, nothing aligned in target
+        let align_res = edlibAlignRs(query, b"", &config);
+        let bio_align = to_bio_alignment(query, b"", &align_res).unwrap();
+        assert_eq!((bio_align.ystart, bio_align.yend), (0, 0));
+        assert_eq!(bio_align.operations, vec![AlignmentOperation::Ins; 5]);
+        //
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_DISTANCE;
+        let align_res = edlibAlignRs(query, target, &config);
+        assert!(to_bio_alignment(query, target, &align_res).is_none());
+    } // end of test_to_bio_alignment
+} // mod tests
@@ -0,0 +1,396 @@
+//! Manipulation of cigar strings without re-aligning.
+//! Cigar strings are sequences of (count, operation) runs, operations being the SAM letters
+//! M, I, D, N, S, H, P, = and X.
+
+use crate::EdlibCigarFormatRs;
+use std::fmt;
+
+/// Error returned when a cigar string is malformed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CigarParseError {
+    /// An operation at given byte offset is not preceded by a count.
+    MissingCount(usize),
+    /// The cigar ends with a count not followed by an operation.
+    MissingOp,
+    /// An unknown operation letter at given byte offset.
+    InvalidOp(char, usize),
+    /// A count does not fit in u32.
+    CountOverflow(usize),
+}
+
+impl fmt::Display for CigarParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CigarParseError::MissingCount(pos) => {
+                write!(f, "cigar operation without count at offset {}", pos)
+            }
+            CigarParseError::MissingOp => write!(f, "cigar ends with a count without operation"),
+            CigarParseError::InvalidOp(op, pos) => {
+                write!(f, "invalid cigar operation {:?} at offset {}", op, pos)
+            }
+            CigarParseError::CountOverflow(pos) => {
+                write!(f, "cigar count overflow at offset {}", pos)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CigarParseError {}
+
+/// Iterator returned by cigar_ops.
+struct CigarOps<'a> {
+    cigar: &'a str,
+    // byte offset of the next run in cigar
+    pos: usize,
+    // set after an error so iteration stops
+    done: bool,
+}
+
+impl<'a> CigarOps<'a> {
+    fn next_run(&mut self) -> Option<Result<(u32, char), CigarParseError>> {
+        let mut count: Option<u32> = None;
+        for (offset, c) in self.cigar[self.pos..].char_indices() {
+            let pos = self.pos + offset;
+            if let Some(digit) = c.to_digit(10) {
+                let n = count
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|n| n.checked_add(digit));
+                match n {
+                    Some(n) => count = Some(n),
+                    None => return Some(Err(CigarParseError::CountOverflow(pos))),
+                }
+            } else if "MIDNSHP=X".contains(c) {
+                self.pos = pos + c.len_utf8();
+                return Some(
+                    count
+                        .map(|n| (n, c))
+                        .ok_or(CigarParseError::MissingCount(pos)),
+                );
+            } else {
+                return Some(Err(CigarParseError::InvalidOp(c, pos)));
+            }
+        }
+        self.pos = self.cigar.len();
+        count.map(|_| Err(CigarParseError::MissingOp))
+    }
+}
+
+impl<'a> Iterator for CigarOps<'a> {
+    type Item = Result<(u32, char), CigarParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let item = self.next_run();
+        self.done = !matches!(item, Some(Ok(_)));
+        item
+    }
+}
+
+/// Iterates over the (count, operation) runs of a cigar string without allocating.
+/// Each item is Ok((count, letter)) or an error on malformed input, after which iteration stops.
+pub fn cigar_ops(cigar: &str) -> impl Iterator<Item = Result<(u32, char), CigarParseError>> + '_ {
+    CigarOps {
+        cigar,
+        pos: 0,
+        done: false,
+    }
+}
+
+/// Converts a cigar in extended format (= and X) to standard format, where matches and mismatches are both M.
+/// Consecutive runs becoming the same operation are merged, for example 3=2X1= gives 6M.
+/// The opposite conversion needs the sequences and is not provided.
+pub fn cigar_extended_to_standard(cigar: &str) -> Result<String, CigarParseError> {
+    let mut merged: Vec<(u32, char)> = Vec::new();
+    for run in cigar_ops(cigar) {
+        let (count, op) = run?;
+        let op = match op {
+            '=' | 'X' => 'M',
+            _ => op,
+        };
+        match merged.last_mut() {
+            Some(last) if last.1 == op => last.0 += count,
+            _ => merged.push((count, op)),
+        }
+    }
+    Ok(merged
+        .iter()
+        .map(|(count, op)| format!("{}{}", count, op))
+        .collect())
+}
+
+/// Builds the cigar of an alignment path in Rust, without going through edlib's edlibAlignmentToCigar.
+/// Consecutive identical operations are run length encoded. Opcodes are written as in edlib :
+/// 0 (match) as = and 3 (mismatch) as X in extended format, both as M in standard format so that
+/// runs of matches and mismatches are merged, 1 (insertion to target) as I and 2 (insertion to query) as D.
+/// The output is identical to edlibAlignmentToCigarRs.
+///
+/// **Panics** if alignment contains an opcode greater than 3.
+pub fn alignment_to_cigar_native(alignment: &[u8], format: EdlibCigarFormatRs) -> String {
+    let mut cigar = String::new();
+    write_cigar(&mut cigar, alignment, format);
+    cigar
+} // end of alignment_to_cigar_native
+
+/// Same as alignment_to_cigar_native but writes the cigar into buf, which is cleared first.
+/// The capacity of buf is kept, so reusing the same buffer across calls avoids an allocation per cigar
+/// in loops producing many cigars.
+///
+/// **Panics** if alignment contains an opcode greater than 3.
+pub fn write_cigar(buf: &mut String, alignment: &[u8], format: EdlibCigarFormatRs) {
+    use std::fmt::Write;
+    buf.clear();
+    for_each_cigar_run(alignment, format, |count, op| {
+        // writing to a String cannot fail
+        write!(buf, "{}{}", count, op).unwrap();
+    });
+} // end of write_cigar
+
+/// Typed cigar operation, as produced from an edlib alignment path by alignment_to_cigar_ops.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CigarOp {
+    /// M, match or mismatch (standard format)
+    Match,
+    /// I, insertion to target (residue of query only)
+    Insertion,
+    /// D, deletion from target (residue of target only)
+    Deletion,
+    /// X, mismatch (extended format)
+    Mismatch,
+    /// =, match (extended format)
+    Equal,
+}
+
+impl CigarOp {
+    /// the SAM letter of the operation
+    pub fn to_char(self) -> char {
+        match self {
+            CigarOp::Match => 'M',
+            CigarOp::Insertion => 'I',
+            CigarOp::Deletion => 'D',
+            CigarOp::Mismatch => 'X',
+            CigarOp::Equal => '=',
+        }
+    }
+}
+
+impl fmt::Display for CigarOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+/// Returns the run length encoded operations of an alignment path as (count, CigarOp), the same runs
+/// as alignment_to_cigar_native writes, so callers need not parse the cigar string back.
+/// In standard format matches and mismatches are both CigarOp::Match, in extended format they are
+/// CigarOp::Equal and CigarOp::Mismatch.
+///
+/// **Panics** if alignment contains an opcode greater than 3.
+pub fn alignment_to_cigar_ops(alignment: &[u8], format: EdlibCigarFormatRs) -> Vec<(u32, CigarOp)> {
+    let mut ops = Vec::new();
+    for_each_cigar_run(alignment, format, |count, op| ops.push((count, op)));
+    ops
+} // end of alignment_to_cigar_ops
+
+// calls f on each (count, operation) run of alignment, without allocating.
+fn for_each_cigar_run(
+    alignment: &[u8],
+    format: EdlibCigarFormatRs,
+    mut f: impl FnMut(u32, CigarOp),
+) {
+    let extended = matches!(format, EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED);
+    let mut run: Option<(u32, CigarOp)> = None;
+    for &op in alignment {
+        let cigar_op = match op {
+            0 if extended => CigarOp::Equal,
+            3 if extended => CigarOp::Mismatch,
+            0 | 3 => CigarOp::Match,
+            1 => CigarOp::Insertion,
+            2 => CigarOp::Deletion,
+            _ => panic!("invalid alignment opcode {}", op),
+        };
+        match run.as_mut() {
+            Some(last) if last.1 == cigar_op => last.0 += 1,
+            _ => {
+                if let Some((count, last_op)) = run.replace((1, cigar_op)) {
+                    f(count, last_op);
+                }
+            }
+        }
+    }
+    if let Some((count, last_op)) = run {
+        f(count, last_op);
+    }
+} // end of for_each_cigar_run
+
+/// Computes the number of reference (target) and query residues spanned by a cigar, as
+/// (reference_consumed, query_consumed).
+/// M, = and X consume both, D and N consume reference only, I and S consume query only, H and P consume none.
+/// With a 0-based start position, the alignment ends on reference at start + reference_consumed - 1.
+pub fn cigar_consumed_lengths(cigar: &str) -> Result<(usize, usize), CigarParseError> {
+    let mut reference = 0usize;
+    let mut query = 0usize;
+    for run in cigar_ops(cigar) {
+        let (count, op) = run?;
+        let count = count as usize;
+        match op {
+            'M' | '=' | 'X' => {
+                reference += count;
+                query += count;
+            }
+            'D' | 'N' => reference += count,
+            'I' | 'S' => query += count,
+            _ => {}
+        }
+    }
+    Ok((reference, query))
+}
+
+//===================================================================
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_extended_to_standard() {
+        assert_eq!(cigar_extended_to_standard("5=2I3X").unwrap(), "5M2I3M");
+        assert_eq!(cigar_extended_to_standard("3=2X1=").unwrap(), "6M");
+        assert_eq!(cigar_extended_to_standard("2S3=1D4M").unwrap(), "2S3M1D4M");
+        assert_eq!(cigar_extended_to_standard("").unwrap(), "");
+        //
+        assert_eq!(
+            cigar_extended_to_standard("5=I"),
+            Err(CigarParseError::MissingCount(2))
+        );
+        assert_eq!(
+            cigar_extended_to_standard("5=2"),
+            Err(CigarParseError::MissingOp)
+        );
+        assert_eq!(
+            cigar_extended_to_standard("5=2Z"),
+            Err(CigarParseError::InvalidOp('Z', 3))
+        );
+        assert_eq!(
+            cigar_extended_to_standard("99999999999M"),
+            Err(CigarParseError::CountOverflow(9))
+        );
+    } // end of test_extended_to_standard
+
+    #[test]
+    fn test_cigar_ops() {
+        let ops: Vec<_> = cigar_ops("5M2I3=1X").collect();
+        assert_eq!(
+            ops,
+            vec![Ok((5, 'M')), Ok((2, 'I')), Ok((3, '=')), Ok((1, 'X'))]
+        );
+        assert_eq!(cigar_ops("").count(), 0);
+        // stops after first error
+        let ops: Vec<_> = cigar_ops("5M2Z3=").collect();
+        assert_eq!(
+            ops,
+            vec![Ok((5, 'M')), Err(CigarParseError::InvalidOp('Z', 3))]
+        );
+        let ops: Vec<_> = cigar_ops("5M2").collect();
+        assert_eq!(ops, vec![Ok((5, 'M')), Err(CigarParseError::MissingOp)]);
+    } // end of test_cigar_ops
+
+    #[test]
+    fn test_cigar_native() {
+        let query = "missing";
+        let target = "mississipi";
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let alignment = align_res.alignment().unwrap();
+        let standard = EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD;
+        let extended = EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED;
+        assert_eq!(alignment_to_cigar_native(alignment, standard), "5M2I");
+        assert_eq!(alignment_to_cigar_native(alignment, extended), "5=2I");
+        // matches and mismatches merge in standard format only
+        let alignment = [0, 0, 3, 0, 1, 2, 2, 3];
+        for format in [standard, extended].iter() {
+            assert_eq!(
+                alignment_to_cigar_native(&alignment, *format),
+                edlibAlignmentToCigarRs(&alignment, format)
+            );
+        }
+        assert_eq!(alignment_to_cigar_native(&alignment, standard), "4M1I2D1M");
+        assert_eq!(alignment_to_cigar_native(&[], standard), "");
+    } // end of test_cigar_native
+
+    #[test]
+    fn test_consumed_lengths() {
+        assert_eq!(cigar_consumed_lengths("5M2I3D"), Ok((8, 7)));
+        assert_eq!(cigar_consumed_lengths("2S5=2X1D3H"), Ok((8, 9)));
+        assert_eq!(cigar_consumed_lengths(""), Ok((0, 0)));
+        assert!(cigar_consumed_lengths("5M2").is_err());
+    } // end of test_consumed_lengths
+
+    #[test]
+    fn test_write_cigar() {
+        let alignment: Vec<u8> = vec![0, 0, 0, 3, 0, 1, 1, 0, 2, 0];
+        let mut buf = String::new();
+        write_cigar(
+            &mut buf,
+            &alignment,
+            EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD,
+        );
+        assert_eq!(buf, "5M2I1M1D1M");
+        write_cigar(
+            &mut buf,
+            &alignment,
+            EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED,
+        );
+        assert_eq!(buf, "3=1X1=2I1=1D1=");
+        assert_eq!(
+            buf,
+            alignment_to_cigar_native(&alignment, EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED)
+        );
+        // the buffer is reused without growing
+        let capacity = buf.capacity();
+        for _ in 0..1000 {
+            write_cigar(
+                &mut buf,
+                &alignment,
+                EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED,
+            );
+        }
+        assert_eq!(buf, "3=1X1=2I1=1D1=");
+        assert_eq!(buf.capacity(), capacity);
+        // empty alignment clears the buffer
+        write_cigar(&mut buf, &[], EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD);
+        assert!(buf.is_empty());
+    } // end of test_write_cigar
+
+    #[test]
+    fn test_alignment_to_cigar_ops() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(b"missing", b"mississipi", &config);
+        let alignment = align_res.alignment().unwrap();
+        assert_eq!(
+            alignment_to_cigar_ops(alignment, EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD),
+            vec![(5, CigarOp::Match), (2, CigarOp::Insertion)]
+        );
+        assert_eq!(
+            alignment_to_cigar_ops(alignment, EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED),
+            vec![(5, CigarOp::Equal), (2, CigarOp::Insertion)]
+        );
+        // the ops give back the cigar string
+        let alignment: Vec<u8> = vec![0, 3, 3, 2, 0, 1];
+        let cigar: String =
+            alignment_to_cigar_ops(&alignment, EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED)
+                .iter()
+                .map(|(count, op)| format!("{}{}", count, op))
+                .collect();
+        assert_eq!(cigar, "1=2X1D1=1I");
+        assert!(alignment_to_cigar_ops(&[], EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD).is_empty());
+    } // end of test_alignment_to_cigar_ops
+} // mod tests
@@ -0,0 +1,131 @@
+//! Distances computed in Rust without calling edlib, for special cases where edlib is overkill.
+
+use crate::Equalities;
+
+/// Hamming distance : number of positions where query and target differ, None if lengths differ.
+/// Only substitutions are considered so this is an upper bound of the NW edit distance,
+/// much faster to compute when indels are not expected.
+/// Bytes are compared strictly, see hamming_distance_with_equalities to use additional equalities.
+pub fn hamming_distance(query: &[u8], target: &[u8]) -> Option<u32> {
+    if query.len() != target.len() {
+        return None;
+    }
+    Some(query.iter().zip(target).filter(|(a, b)| a != b).count() as u32)
+}
+
+/// Same as hamming_distance but positions with bytes declared equal by equalities are not counted,
+/// consistently with edlib alignments computed with the same additional equalities.
+pub fn hamming_distance_with_equalities(
+    query: &[u8],
+    target: &[u8],
+    equalities: &Equalities,
+) -> Option<u32> {
+    if query.len() != target.len() {
+        return None;
+    }
+    Some(
+        query
+            .iter()
+            .zip(target)
+            .filter(|(&a, &b)| !equalities.equals(a, b))
+            .count() as u32,
+    )
+}
+
+/// Damerau-Levenshtein distance : minimal number of insertions, deletions, substitutions and transpositions
+/// of two adjacent bytes to turn query into target, so "ab" and "ba" are at distance 1 instead of 2.
+/// This is the unrestricted distance (Lowrance-Wagner algorithm) where a transposed pair can be further edited,
+/// giving 2 for "ca" and "abc" where the restricted optimal string alignment distance gives 3.
+///
+/// This does **not** use edlib : it is a plain dynamic programming in O(query.len() * target.len()) time
+/// and memory, to keep for short sequences (words, identifiers...) where edlib bit-parallel speed is not needed.
+/// Bytes are compared strictly.
+pub fn damerau_levenshtein(query: &[u8], target: &[u8]) -> u32 {
+    let (n, m) = (query.len(), target.len());
+    let max_dist = n + m;
+    // d is (n + 2) x (m + 2), row 0 and column 0 hold max_dist as sentinels, d[i + 1][j + 1] is the
+    // distance between query[..i] and target[..j]
+    let width = m + 2;
+    let mut d = vec![0usize; (n + 2) * width];
+    d[0] = max_dist;
+    for i in 0..=n {
+        d[(i + 1) * width] = max_dist;
+        d[(i + 1) * width + 1] = i;
+    }
+    for j in 0..=m {
+        d[j + 1] = max_dist;
+        d[width + j + 1] = j;
+    }
+    // last row (1-based) where each byte was seen in query
+    let mut last_row = [0usize; 256];
+    for i in 1..=n {
+        // last column (1-based) in this row where query[i - 1] matched target
+        let mut last_match_col = 0;
+        for j in 1..=m {
+            let k = last_row[target[j - 1] as usize];
+            let l = last_match_col;
+            let cost = if query[i - 1] == target[j - 1] {
+                last_match_col = j;
+                0
+            } else {
+                1
+            };
+            let substitution = d[i * width + j] + cost;
+            let insertion = d[(i + 1) * width + j] + 1;
+            let deletion = d[i * width + j + 1] + 1;
+            let transposition = d[k * width + l] + (i - k - 1) + 1 + (j - l - 1);
+            d[(i + 1) * width + j + 1] =
+                substitution.min(insertion).min(deletion).min(transposition);
+        }
+        last_row[query[i - 1] as usize] = i;
+    }
+    d[(n + 1) * width + m + 1] as u32
+} // end of damerau_levenshtein
+
+//===================================================================
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_hamming_distance() {
+        assert_eq!(hamming_distance(b"ACGT", b"ACGT"), Some(0));
+        assert_eq!(hamming_distance(b"ACGT", b"ACCA"), Some(2));
+        assert_eq!(hamming_distance(b"", b""), Some(0));
+        assert_eq!(hamming_distance(b"ACGT", b"ACG"), None);
+    } // end of test_hamming_distance
+
+    #[test]
+    fn test_hamming_distance_with_equalities() {
+        let equalities = Equalities::new(&[crate::EdlibEqualityPairRs::new(b'A', b'N')]);
+        assert_eq!(
+            hamming_distance_with_equalities(b"ACGT", b"NCGN", &equalities),
+            Some(1)
+        );
+        assert_eq!(
+            hamming_distance_with_equalities(b"ACGT", b"NCGN", &Equalities::default()),
+            Some(2)
+        );
+    } // end of test_hamming_distance_with_equalities
+
+    #[test]
+    fn test_damerau_levenshtein() {
+        // transposition and insertion, optimal string alignment would give 3
+        assert_eq!(damerau_levenshtein(b"ca", b"abc"), 2);
+        assert_eq!(damerau_levenshtein(b"ab", b"ba"), 1);
+        assert_eq!(damerau_levenshtein(b"abcdef", b"abdcef"), 1);
+        assert_eq!(damerau_levenshtein(b"kitten", b"sitting"), 3);
+        assert_eq!(damerau_levenshtein(b"", b"abc"), 3);
+        assert_eq!(damerau_levenshtein(b"abc", b""), 3);
+        assert_eq!(damerau_levenshtein(b"", b""), 0);
+        assert_eq!(damerau_levenshtein(b"abc", b"abc"), 0);
+        // without transpositions it is edlib NW distance
+        let config = crate::EdlibAlignConfigRs::default();
+        let nw = crate::edlibAlignRs(b"kitten", b"sitting", &config);
+        assert_eq!(nw.editDistance, 3);
+        let nw = crate::edlibAlignRs(b"ab", b"ba", &config);
+        assert_eq!(nw.editDistance, 2);
+    } // end of test_damerau_levenshtein
+} // mod tests
@@ -0,0 +1,187 @@
+//! Helpers to build lists of EdlibEqualityPairRs for EdlibAlignConfigRs.additionalequalities.
+
+use crate::*;
+
+/// IUPAC nucleotide ambiguity codes with the bases they stand for.
+const IUPAC_CODES: [(char, &str); 11] = [
+    ('R', "AG"),
+    ('Y', "CT"),
+    ('S', "CG"),
+    ('W', "AT"),
+    ('K', "GT"),
+    ('M', "AC"),
+    ('B', "CGT"),
+    ('D', "AGT"),
+    ('H', "ACT"),
+    ('V', "ACG"),
+    ('N', "ACGT"),
+];
+
+/// Builds equality pairs from (a,b) char pairs, adding both (a,b) and (b,a).
+/// edlib itself makes each pair symmetric, but having both directions in the list keeps it
+/// self describing and independent of that implementation detail.
+/// chars must be ASCII as edlib works on bytes.
+pub fn symmetric_equalities(pairs: &[(char, char)]) -> Vec<EdlibEqualityPairRs> {
+    let mut equalities = Vec::<EdlibEqualityPairRs>::with_capacity(2 * pairs.len());
+    for &(a, b) in pairs {
+        equalities.push(EdlibEqualityPairRs::from_chars(a, b));
+        equalities.push(EdlibEqualityPairRs::from_chars(b, a));
+    }
+    equalities
+}
+
+/// Returns the standard IUPAC nucleotide ambiguity equalities: each ambiguity code
+/// (R, Y, S, W, K, M, B, D, H, V, N) is equal to each base it stands for, in both directions.
+/// Only uppercase codes and bases are generated, ambiguity codes are not made equal to each other.
+pub fn iupac_equalities() -> Vec<EdlibEqualityPairRs> {
+    let pairs: Vec<(char, char)> = IUPAC_CODES
+        .iter()
+        .flat_map(|&(code, bases)| bases.chars().map(move |base| (code, base)))
+        .collect();
+    symmetric_equalities(&pairs)
+}
+
+/// Returns equalities making each ASCII uppercase letter equal to its lowercase counterpart, in both directions.
+/// Use with EdlibAlignConfigRs::case_insensitive to get case insensitive edit distance.
+pub fn ascii_case_insensitive_equalities() -> Vec<EdlibEqualityPairRs> {
+    let pairs: Vec<(char, char)> = ('A'..='Z').map(|c| (c, c.to_ascii_lowercase())).collect();
+    symmetric_equalities(&pairs)
+}
+
+/// Returns a canonical list of equalities : for each pair (a,b) the reverse (b,a) is added if missing
+/// and exact duplicates are removed, pairs keeping their first occurrence order.
+/// edlib already makes each pair symmetric, so alignments are unchanged, but the list becomes self describing
+/// (as with symmetric_equalities) and duplicates do not cost a pass in edlib's equality setup.
+/// See EdlibAlignConfigOwned::with_normalized_equalities to normalize a configuration.
+pub fn normalize_equalities(pairs: &[EdlibEqualityPairRs]) -> Vec<EdlibEqualityPairRs> {
+    let mut seen = std::collections::HashSet::<(u8, u8)>::with_capacity(2 * pairs.len());
+    let mut equalities = Vec::<EdlibEqualityPairRs>::with_capacity(2 * pairs.len());
+    for pair in pairs {
+        let (a, b) = (pair.first(), pair.second());
+        for &(x, y) in &[(a, b), (b, a)] {
+            if seen.insert((x, y)) {
+                equalities.push(EdlibEqualityPairRs::new(x, y));
+            }
+        }
+    }
+    equalities
+}
+
+/// Byte equality as edlib sees it : each byte is equal to itself and to the bytes it is paired with
+/// by additional equalities, in both directions as edlib makes pairs symmetric.
+/// Used by the Rust side helpers (hamming distance, alignment validation) to compare residues as edlib does.
+#[derive(Debug, Clone)]
+pub struct Equalities {
+    // bit b of row a is set if a and b are declared equal
+    table: Vec<[u64; 4]>,
+}
+
+impl Equalities {
+    /// Builds the equality relation from the pairs given in EdlibAlignConfigRs.additionalequalities.
+    pub fn new(pairs: &[EdlibEqualityPairRs]) -> Self {
+        let mut table = vec![[0u64; 4]; 256];
+        for pair in pairs {
+            let (a, b) = (pair.first() as usize, pair.second() as usize);
+            table[a][b / 64] |= 1 << (b % 64);
+            table[b][a / 64] |= 1 << (a % 64);
+        }
+        Equalities { table }
+    }
+
+    /// true if a and b are the same byte or are declared equal by a pair.
+    pub fn equals(&self, a: u8, b: u8) -> bool {
+        a == b || self.table[a as usize][b as usize / 64] & (1 << (b % 64)) != 0
+    }
+}
+
+impl Default for Equalities {
+    /// strict byte equality, no pair.
+    fn default() -> Self {
+        Equalities::new(&[])
+    }
+}
+
+//===================================================================
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_symmetric_equalities() {
+        let equalities = symmetric_equalities(&[('A', 'N')]);
+        assert_eq!(equalities.len(), 2);
+        assert_eq!(equalities[0].first, 'A' as c_char);
+        assert_eq!(equalities[0].second, 'N' as c_char);
+        assert_eq!(equalities[1].first, 'N' as c_char);
+        assert_eq!(equalities[1].second, 'A' as c_char);
+    } // end of test_symmetric_equalities
+
+    #[test]
+    fn test_iupac_equalities() {
+        let equalities = iupac_equalities();
+        let mut config = EdlibAlignConfigRs::default();
+        let align_res = edlibAlignRs(b"ACGT", b"ACRT", &config);
+        assert_eq!(align_res.editDistance, 1);
+        config.additionalequalities = &equalities;
+        let align_res = edlibAlignRs(b"ACGT", b"ACRT", &config);
+        assert_eq!(align_res.status, EDLIB_STATUS_OK);
+        assert_eq!(align_res.editDistance, 0);
+        let align_res = edlibAlignRs(b"ACGT", b"NNNN", &config);
+        assert_eq!(align_res.editDistance, 0);
+        // R is A or G, not C
+        let align_res = edlibAlignRs(b"ACGT", b"ARGT", &config);
+        assert_eq!(align_res.editDistance, 1);
+    } // end of test_iupac_equalities
+
+    #[test]
+    fn test_case_insensitive() {
+        let equalities = ascii_case_insensitive_equalities();
+        assert_eq!(equalities.len(), 52);
+        let config = EdlibAlignConfigRs::case_insensitive(&equalities);
+        let align_res = edlibAlignRs(b"Hello", b"hello", &config);
+        assert_eq!(align_res.status, EDLIB_STATUS_OK);
+        assert_eq!(align_res.editDistance, 0);
+        let align_res = edlibAlignRs(b"HELLO", b"hello", &config);
+        assert_eq!(align_res.editDistance, 0);
+        // without equalities
+        let align_res = edlibAlignRs(b"Hello", b"hello", &EdlibAlignConfigRs::default());
+        assert_eq!(align_res.editDistance, 1);
+        let align_res = edlibAlignRs(b"HELLo", b"hello", &EdlibAlignConfigRs::default());
+        assert_eq!(align_res.editDistance, 4);
+    } // end of test_case_insensitive
+
+    #[test]
+    fn test_equalities_equals() {
+        let equalities = Equalities::new(&[EdlibEqualityPairRs::new(b'A', b'N')]);
+        assert!(equalities.equals(b'A', b'N'));
+        assert!(equalities.equals(b'N', b'A'));
+        assert!(equalities.equals(b'C', b'C'));
+        assert!(!equalities.equals(b'C', b'N'));
+        assert!(!Equalities::default().equals(b'A', b'N'));
+        let equalities = Equalities::new(&iupac_equalities());
+        assert!(equalities.equals(b'N', b'A'));
+        assert!(equalities.equals(b'G', b'R'));
+        assert!(!equalities.equals(b'C', b'R'));
+    } // end of test_equalities_equals
+
+    #[test]
+    fn test_normalize_equalities() {
+        let pairs = [
+            EdlibEqualityPairRs::new(b'A', b'N'),
+            EdlibEqualityPairRs::new(b'A', b'N'),
+        ];
+        let normalized = normalize_equalities(&pairs);
+        let bytes: Vec<(u8, u8)> = normalized.iter().map(|p| (p.first(), p.second())).collect();
+        assert_eq!(bytes, vec![(b'A', b'N'), (b'N', b'A')]);
+        // already symmetric input is unchanged
+        let iupac = iupac_equalities();
+        assert_eq!(normalize_equalities(&iupac).len(), iupac.len());
+        // a reflexive pair is kept once
+        assert_eq!(
+            normalize_equalities(&[EdlibEqualityPairRs::new(b'A', b'A')]).len(),
+            1
+        );
+    } // end of test_normalize_equalities
+} // mod tests
@@ -0,0 +1,149 @@
+//! Streaming alignment of the records of a FASTA file against one target (feature fasta).
+
+use crate::batch::read_line_bytes;
+use crate::*;
+use std::io::{BufReader, Read};
+
+/// Iterator returned by align_fasta.
+struct FastaAligner<'a, R: Read> {
+    reader: BufReader<R>,
+    // buffer of the line being read
+    line: Vec<u8>,
+    // name of the record whose header was just read
+    next_name: Option<String>,
+    // set at the first read error, iteration is then over
+    failed: bool,
+    target: &'a [u8],
+    config: &'a EdlibAlignConfigRs<'a>,
+}
+
+impl<'a, R: Read> Iterator for FastaAligner<'a, R> {
+    type Item = (String, EdlibAlignResultRs);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let mut seq = Vec::<u8>::new();
+        loop {
+            match read_line_bytes(&mut self.reader, &mut self.line) {
+                Ok(true) => {}
+                Ok(false) => break,
+                Err(_) => {
+                    // the record being read is incomplete, do not align it
+                    self.failed = true;
+                    self.next_name = None;
+                    return None;
+                }
+            }
+            let end = self
+                .line
+                .iter()
+                .rposition(|c| !c.is_ascii_whitespace())
+                .map_or(0, |i| i + 1);
+            let line = &self.line[..end];
+            if let Some(header) = line.strip_prefix(b">") {
+                let name = String::from_utf8_lossy(header)
+                    .split_whitespace()
+                    .next()
+                    .unwrap_or("")
+                    .to_string();
+                if let Some(previous) = self.next_name.replace(name) {
+                    let align_res = edlibAlignRs(&seq, self.target, self.config);
+                    return Some((previous, align_res));
+                }
+            } else if self.next_name.is_some() {
+                seq.extend_from_slice(line);
+            }
+        }
+        let name = self.next_name.take()?;
+        Some((name, edlibAlignRs(&seq, self.target, self.config)))
+    }
+} // end of impl Iterator for FastaAligner
+
+/// Aligns each record of a FASTA stream against target, lazily : records are read and aligned one at a time
+/// so the file is never loaded in memory.
+/// Only plain FASTA is handled : a header line starting with '>' then sequence lines (possibly wrapped) until
+/// the next header. The record name is the first word of the header. Lines before the first header are ignored.
+/// Lines are read as bytes, so sequences need not be valid UTF-8 (names are converted lossily).
+/// Iteration stops at the first read error, the record being read when it occurs is not returned.
+pub fn align_fasta<'a, R: Read + 'a>(
+    reader: R,
+    target: &'a [u8],
+    config: &'a EdlibAlignConfigRs<'a>,
+) -> impl Iterator<Item = (String, EdlibAlignResultRs)> + 'a {
+    FastaAligner {
+        reader: BufReader::new(reader),
+        line: Vec::new(),
+        next_name: None,
+        failed: false,
+        target,
+        config,
+    }
+}
+
+//===================================================================
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_align_fasta() {
+        let fasta = ">read1 first read\nACCTCTG\n>read2\nACTC\nTGAAA\n\n>read3\nTTTT\n";
+        let target = b"ACTCTGAAA";
+        let config = EdlibAlignConfigRs::default();
+        let results: Vec<(String, EdlibAlignResultRs)> =
+            align_fasta(fasta.as_bytes(), target, &config).collect();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].0, "read1");
+        assert_eq!(results[0].1.editDistance, 4);
+        assert_eq!(results[1].0, "read2");
+        assert_eq!(results[1].1.editDistance, 0);
+        assert_eq!(results[2].0, "read3");
+        assert_eq!(results[2].1.editDistance, 7);
+        //
+        assert_eq!(align_fasta("".as_bytes(), target, &config).count(), 0);
+    } // end of test_align_fasta
+
+    // gives data then fails
+    struct FailingReader<'a> {
+        data: &'a [u8],
+    }
+
+    impl<'a> Read for FailingReader<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.data.is_empty() {
+                return Err(std::io::Error::other("read failed"));
+            }
+            self.data.read(buf)
+        }
+    }
+
+    #[test]
+    fn test_align_fasta_bytes_and_errors() {
+        let config = EdlibAlignConfigRs::default();
+        // a non UTF-8 byte is part of the sequence
+        let fasta: &[u8] = b">r1\nACGT\nAC\xffGT\nTTTT\n>r2\nACGT\n";
+        let target = b"ACGTAC\xffGTTTTT";
+        let results: Vec<(String, EdlibAlignResultRs)> =
+            align_fasta(fasta, target, &config).collect();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, "r1");
+        assert_eq!(results[0].1.editDistance, 0);
+        assert_eq!(results[1].0, "r2");
+        assert_eq!(results[1].1.editDistance, 9);
+        // a read error in r2 : r1 is returned, the incomplete r2 is not, and iteration is over
+        let reader = FailingReader {
+            data: b">r1\nACGT\n>r2\nACG",
+        };
+        let mut aligner = align_fasta(reader, target, &config);
+        assert_eq!(
+            aligner.next().map(|(name, _)| name),
+            Some(String::from("r1"))
+        );
+        assert!(aligner.next().is_none());
+        assert!(aligner.next().is_none());
+    } // end of test_align_fasta_bytes_and_errors
+} // mod tests
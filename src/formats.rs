@@ -0,0 +1,646 @@
+//! Serialization of alignment results to standard interchange formats (PAF, SAM) and text visualization.
+//! All functions need a result computed with task EDLIB_TASK_PATH and return None otherwise.
+
+use crate::*;
+use std::io;
+
+/// Count the number of matching residues (opcode 0) in an alignment path.
+fn count_matches(alignment: &[u8]) -> usize {
+    alignment
+        .iter()
+        .filter(|&&op| op == EdlibEdopRs::EDLIB_EDOP_MATCH as u8)
+        .count()
+}
+
+/// Count insertions to target (query only residues) at the start and at the end of an alignment path.
+fn count_end_insertions(alignment: &[u8]) -> (usize, usize) {
+    let insert = EdlibEdopRs::EDLIB_EDOP_INSERT as u8;
+    let leading = alignment.iter().take_while(|&&op| op == insert).count();
+    let trailing = alignment[leading..]
+        .iter()
+        .rev()
+        .take_while(|&&op| op == insert)
+        .count();
+    (leading, trailing)
+}
+
+/// Numbers of leading and trailing insertions of alignment to write as soft clips : the query residues
+/// hanging out of target, before target position 0 (start is 0) or after target_len - 1 (end is target_len - 1).
+fn soft_clipped_ends(alignment: &[u8], start: i32, end: i32, target_len: usize) -> (usize, usize) {
+    let (mut leading, mut trailing) = count_end_insertions(alignment);
+    if start != 0 {
+        leading = 0;
+    }
+    if end as i64 != target_len as i64 - 1 {
+        trailing = 0;
+    }
+    (leading, trailing)
+}
+
+/// Cigar of alignment where the leading first and trailing last opcodes, which must be insertions,
+/// are written as soft clips.
+fn cigar_with_clips(
+    alignment: &[u8],
+    leading: usize,
+    trailing: usize,
+    format: EdlibCigarFormatRs,
+) -> String {
+    let mut cigar = String::new();
+    if leading > 0 {
+        cigar.push_str(&format!("{}S", leading));
+    }
+    let middle = &alignment[leading..alignment.len() - trailing];
+    if !middle.is_empty() {
+        cigar.push_str(&alignment_to_cigar_native(middle, format));
+    }
+    if trailing > 0 {
+        cigar.push_str(&format!("{}S", trailing));
+    }
+    cigar
+}
+
+/// Builds the cigar of result alignment path with soft clips (S) for query residues hanging out of target.
+/// In HW and SHW modes, when the query overhangs the start (end) of target, the alignment starts (ends) with
+/// insertions at target position 0 (target.len() - 1). These are written as soft clips instead of insertions.
+/// mode is the one result was computed with, as it is not kept in the result : in EDLIB_MODE_NW the whole
+/// query is aligned to the whole target, so nothing is clipped and the cigar is the one of edlibAlignmentToCigarRs.
+///
+/// Returns None if result has no alignment path (task was not EDLIB_TASK_PATH or distance larger than k),
+/// or if query or target do not fit the path (the sequences are not the ones aligned).
+pub fn alignment_to_cigar_with_clips(
+    query: &[u8],
+    target: &[u8],
+    result: &EdlibAlignResultRs,
+    mode: EdlibAlignModeRs,
+    format: EdlibCigarFormatRs,
+) -> Option<String> {
+    let alignment = result.alignment()?;
+    let start = *result.start_locations()?.first()?;
+    let end = *result.end_locations()?.first()?;
+    let query_residues = alignment
+        .iter()
+        .filter(|&&op| op != EdlibEdopRs::EDLIB_EDOP_DELETE as u8)
+        .count();
+    if query_residues != query.len() || start < 0 || end as i64 >= target.len() as i64 {
+        return None;
+    }
+    if matches!(mode, EdlibAlignModeRs::EDLIB_MODE_NW) {
+        return Some(edlibAlignmentToCigarRs(alignment, &format));
+    }
+    let (leading, trailing) = soft_clipped_ends(alignment, start, end, target.len());
+    Some(cigar_with_clips(alignment, leading, trailing, format))
+}
+
+/// Emits one PAF (Pairwise mApping Format) line for an alignment of query against target.
+/// see https://github.com/lh3/miniasm/blob/master/PAF.md
+///
+/// Columns are : query name, query length, query start, query end, strand, target name, target length,
+/// target start, target end, number of residue matches, alignment block length, mapping quality (always 255)
+/// followed by a cg:Z: tag holding the cigar in extended format.
+/// Coordinates are 0-based and ends are exclusive, as required by PAF.
+/// edlib always aligns the whole query so query start and end are 0 and query length.
+///
+/// Returns None if result has no alignment path (task was not EDLIB_TASK_PATH or distance larger than k).
+pub fn alignment_to_paf(
+    query_name: &str,
+    query: &[u8],
+    target_name: &str,
+    target: &[u8],
+    result: &EdlibAlignResultRs,
+) -> Option<String> {
+    let alignment = result.alignment()?;
+    let target_start = *result.start_locations()?.first()?;
+    let target_end = *result.end_locations()?.first()? + 1;
+    let (matches, block_length) = result.paf_match_block()?;
+    let cigar = edlibAlignmentToCigarRs(alignment, &EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED);
+    Some(format!(
+        "{}\t{}\t{}\t{}\t+\t{}\t{}\t{}\t{}\t{}\t{}\t255\tcg:Z:{}",
+        query_name,
+        query.len(),
+        0,
+        query.len(),
+        target_name,
+        target.len(),
+        target_start,
+        target_end,
+        matches,
+        block_length,
+        cigar
+    ))
+} // end of alignment_to_paf
+
+/// Emits one SAM line for an alignment of query against a target named target_name.
+/// see http://samtools.github.io/hts-specs/SAMv1.pdf
+///
+/// The 11 mandatory fields are written : QNAME, FLAG, RNAME, POS, MAPQ (always 255), CIGAR (standard format),
+/// RNEXT (\*), PNEXT (0), TLEN (0), SEQ and QUAL (\*).
+/// POS is 1-based, converted from edlib's 0-based startLocations\[0\].
+/// Insertions at the very start (end) of the alignment, when it starts (ends) at the first (last) target position,
+/// are query bases hanging out of the target (HW and SHW modes) and are written as soft clips (S),
+/// as in alignment_to_cigar_with_clips.
+///
+/// Returns None if result has no alignment path (task was not EDLIB_TASK_PATH or distance larger than k).
+pub fn alignment_to_sam(
+    query_name: &str,
+    query: &[u8],
+    target_name: &str,
+    target: &[u8],
+    result: &EdlibAlignResultRs,
+    flag: u16,
+) -> Option<String> {
+    let alignment = result.alignment()?;
+    let start = *result.start_locations()?.first()?;
+    let end = *result.end_locations()?.first()?;
+    let pos = start + 1;
+    let (leading, trailing) = soft_clipped_ends(alignment, start, end, target.len());
+    let cigar = cigar_with_clips(
+        alignment,
+        leading,
+        trailing,
+        EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD,
+    );
+    Some(format!(
+        "{}\t{}\t{}\t{}\t255\t{}\t*\t0\t0\t{}\t*",
+        query_name,
+        flag,
+        target_name,
+        pos,
+        cigar,
+        String::from_utf8_lossy(query)
+    ))
+} // end of alignment_to_sam
+
+// residue at position i of seq as char, an error if the path does not fit seq
+fn residue(seq: &[u8], i: usize) -> io::Result<char> {
+    seq.get(i).map(|&c| c as char).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "alignment path does not fit sequences",
+        )
+    })
+}
+
+/// Writes a BLAST style visualization of the alignment : a query row, a middle row with '|' under matches
+/// and ' ' under mismatches and gaps, and a target row, with '-' for gaps.
+/// Rows are wrapped every width columns and each block of 3 rows is followed by an empty line.
+/// Each row starts with the 0-based position of its first residue and ends with the position of its last one.
+/// Target positions are offset by startLocations\[0\] so they are correct in HW and SHW modes.
+///
+/// Returns an error of kind InvalidInput if result has no alignment path, width is 0 or the path does not fit
+/// query and target (the sequences are not the ones aligned).
+pub fn write_alignment<W: io::Write>(
+    w: &mut W,
+    query: &[u8],
+    target: &[u8],
+    result: &EdlibAlignResultRs,
+    width: usize,
+) -> io::Result<()> {
+    if width == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "width must be positive",
+        ));
+    }
+    let pairs = result.coordinate_pairs(0).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, "no alignment path in result")
+    })?;
+    let label_width = query.len().max(target.len()).to_string().len();
+    let mut query_pos = 0;
+    let mut target_pos = *result.start_locations().unwrap().first().unwrap() as usize;
+    for block in pairs.chunks(width) {
+        let mut query_row = String::with_capacity(width);
+        let mut middle_row = String::with_capacity(width);
+        let mut target_row = String::with_capacity(width);
+        let (query_start, target_start) = (query_pos, target_pos);
+        for &(q, t) in block {
+            let q_char = q.map(|i| residue(query, i)).transpose()?;
+            let t_char = t.map(|i| residue(target, i)).transpose()?;
+            query_row.push(q_char.unwrap_or('-'));
+            target_row.push(t_char.unwrap_or('-'));
+            match (q_char, t_char) {
+                (Some(a), Some(b)) if a == b => middle_row.push('|'),
+                _ => middle_row.push(' '),
+            }
+            query_pos += q.is_some() as usize;
+            target_pos += t.is_some() as usize;
+        }
+        writeln!(
+            w,
+            "Q {:>lw$} {} {}",
+            query_start,
+            query_row,
+            query_pos as i64 - 1,
+            lw = label_width
+        )?;
+        writeln!(w, "  {:>lw$} {}", "", middle_row, lw = label_width)?;
+        writeln!(
+            w,
+            "T {:>lw$} {} {}",
+            target_start,
+            target_row,
+            target_pos as i64 - 1,
+            lw = label_width
+        )?;
+        writeln!(w)?;
+    }
+    Ok(())
+} // end of write_alignment
+
+/// A variant of query relative to target taken as the reference, in the VCF way : ref_allele at pos
+/// in reference is replaced by alt_allele in query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    /// 0-based position in reference of the first base of ref_allele.
+    pub pos: usize,
+    /// reference bases replaced.
+    pub ref_allele: String,
+    /// query bases replacing them.
+    pub alt_allele: String,
+}
+
+// bytes of an allele as String
+fn allele(parts: &[&[u8]]) -> String {
+    String::from_utf8_lossy(&parts.concat()).into_owned()
+}
+
+/// Converts the alignment path of result to the list of variants of query relative to target, ordered by position.
+/// Each mismatch gives a SNV. Each run of insertions (query only bases) or deletions (target only bases)
+/// gives an indel anchored, as in VCF, on the reference base before it, which starts both alleles.
+/// An indel at the very start of target is anchored on the base after it, which ends both alleles.
+/// Positions are 0-based positions in target (add 1 for VCF POS) plus ref_start, the position of target
+/// in a longer reference if target is a part of it.
+/// In HW and SHW modes, query bases overhanging the target ends are reported as insertions.
+///
+/// Returns None if result has no alignment path, if ref_start is negative or if the path does not fit
+/// query and target.
+pub fn alignment_to_variants(
+    query: &[u8],
+    target: &[u8],
+    result: &EdlibAlignResultRs,
+    ref_start: i32,
+) -> Option<Vec<Variant>> {
+    if ref_start < 0 {
+        return None;
+    }
+    let alignment = result.alignment()?;
+    let mut target_pos = *result.start_locations()?.first()? as usize;
+    let mut query_pos = 0usize;
+    let mut variants = Vec::<Variant>::new();
+    let mut i = 0;
+    while i < alignment.len() {
+        let op = alignment[i];
+        let run = alignment[i..].iter().take_while(|&&o| o == op).count();
+        match op {
+            0 => {}
+            3 => {
+                for k in 0..run {
+                    variants.push(Variant {
+                        pos: ref_start as usize + target_pos + k,
+                        ref_allele: allele(&[target.get(target_pos + k..=target_pos + k)?]),
+                        alt_allele: allele(&[query.get(query_pos + k..=query_pos + k)?]),
+                    });
+                }
+            }
+            1 | 2 => {
+                let (inserted, deleted): (&[u8], &[u8]) = if op == 1 {
+                    (query.get(query_pos..query_pos + run)?, &[])
+                } else {
+                    (&[], target.get(target_pos..target_pos + run)?)
+                };
+                let variant = if target_pos > 0 {
+                    let anchor = target.get(target_pos - 1..target_pos)?;
+                    Variant {
+                        pos: ref_start as usize + target_pos - 1,
+                        ref_allele: allele(&[anchor, deleted]),
+                        alt_allele: allele(&[anchor, inserted]),
+                    }
+                } else {
+                    let after = target_pos + deleted.len();
+                    let anchor = target.get(after..=after)?;
+                    Variant {
+                        pos: ref_start as usize + target_pos,
+                        ref_allele: allele(&[deleted, anchor]),
+                        alt_allele: allele(&[inserted, anchor]),
+                    }
+                };
+                variants.push(variant);
+            }
+            _ => return None,
+        }
+        if op != EdlibEdopRs::EDLIB_EDOP_DELETE as u8 {
+            query_pos += run;
+        }
+        if op != EdlibEdopRs::EDLIB_EDOP_INSERT as u8 {
+            target_pos += run;
+        }
+        i += run;
+    }
+    Some(variants)
+} // end of alignment_to_variants
+
+/// Returns the (query_pos, target_pos) points of a dotplot of the alignment : one point per match opcode,
+/// target positions being offset by startLocations\[0\]. Points come in path order, so both coordinates increase.
+///
+/// Returns None if result has no alignment path or start location, or if a point is out of query or target
+/// (the sequences are not the ones aligned).
+pub fn dotplot_points(
+    query: &[u8],
+    target: &[u8],
+    result: &EdlibAlignResultRs,
+) -> Option<Vec<(usize, usize)>> {
+    let alignment = result.alignment()?;
+    let pairs = result.coordinate_pairs(0)?;
+    let mut points = Vec::with_capacity(count_matches(alignment));
+    for (&op, &(query_pos, target_pos)) in alignment.iter().zip(pairs.iter()) {
+        if op == EdlibEdopRs::EDLIB_EDOP_MATCH as u8 {
+            let point = (query_pos?, target_pos?);
+            if point.0 >= query.len() || point.1 >= target.len() {
+                return None;
+            }
+            points.push(point);
+        }
+    }
+    Some(points)
+} // end of dotplot_points
+
+//===================================================================
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_paf_hw() {
+        let query = "missing";
+        let target = "mississipi";
+        //
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let paf = alignment_to_paf("q", query.as_bytes(), "t", target.as_bytes(), &align_res);
+        assert_eq!(
+            paf,
+            Some(String::from(
+                "q\t7\t0\t7\t+\tt\t10\t0\t5\t5\t7\t255\tcg:Z:5=2I"
+            ))
+        );
+        // no path without EDLIB_TASK_PATH
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let paf = alignment_to_paf("q", query.as_bytes(), "t", target.as_bytes(), &align_res);
+        assert!(paf.is_none());
+    } // end of test_paf_hw
+
+    #[test]
+    fn test_sam_pos() {
+        let query = "ACGT";
+        let target = "TTACGTTT";
+        //
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.start_locations().unwrap()[0], 2);
+        let sam =
+            alignment_to_sam("q", query.as_bytes(), "t", target.as_bytes(), &align_res, 0).unwrap();
+        // 0-based start 2 is POS 3 in SAM
+        assert_eq!(sam, "q\t0\tt\t3\t255\t4M\t*\t0\t0\tACGT\t*");
+    } // end of test_sam_pos
+
+    #[test]
+    fn test_sam_soft_clip() {
+        let query = "GGACGT";
+        let target = "ACGTAAAA";
+        //
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let sam = alignment_to_sam(
+            "q",
+            query.as_bytes(),
+            "t",
+            target.as_bytes(),
+            &align_res,
+            16,
+        )
+        .unwrap();
+        let fields: Vec<&str> = sam.split('\t').collect();
+        assert_eq!(fields.len(), 11);
+        assert_eq!(fields[1], "16");
+        assert_eq!(fields[3], "1");
+        assert_eq!(fields[5], "2S4M");
+        // insertions inside target are not clipped, same cigar as alignment_to_cigar_with_clips
+        let query = "ACGTCC";
+        let target = "TTACGTAAAA";
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.start_locations().unwrap()[0], 2);
+        let sam =
+            alignment_to_sam("q", query.as_bytes(), "t", target.as_bytes(), &align_res, 0).unwrap();
+        let fields: Vec<&str> = sam.split('\t').collect();
+        assert_eq!(fields[5], "4M2I");
+        let cigar = alignment_to_cigar_with_clips(
+            query.as_bytes(),
+            target.as_bytes(),
+            &align_res,
+            config.mode,
+            EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD,
+        );
+        assert_eq!(cigar.as_deref(), Some(fields[5]));
+    } // end of test_sam_soft_clip
+
+    #[test]
+    fn test_cigar_with_clips() {
+        let query = "GGACGT";
+        let target = "ACGTAAAA";
+        //
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let format = EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED;
+        let cigar = alignment_to_cigar_with_clips(
+            query.as_bytes(),
+            target.as_bytes(),
+            &align_res,
+            config.mode,
+            format,
+        );
+        assert_eq!(cigar, Some(String::from("2S4=")));
+        let cigar = edlibAlignmentToCigarRs(align_res.alignment().unwrap(), &format);
+        assert_eq!(cigar, "2I4=");
+        // overhang at target end
+        let query = "ACGTGG";
+        let target = "TTTTACGT";
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let cigar = alignment_to_cigar_with_clips(
+            query.as_bytes(),
+            target.as_bytes(),
+            &align_res,
+            config.mode,
+            format,
+        );
+        assert_eq!(cigar, Some(String::from("4=2S")));
+        let target = "ACGTAAAA";
+        // no overhang, same as edlib cigar
+        let query = "ACGTA";
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let cigar = alignment_to_cigar_with_clips(
+            query.as_bytes(),
+            target.as_bytes(),
+            &align_res,
+            config.mode,
+            format,
+        );
+        assert_eq!(cigar, Some(String::from("5=")));
+        // query not the one aligned
+        let cigar = alignment_to_cigar_with_clips(
+            b"ACG",
+            target.as_bytes(),
+            &align_res,
+            config.mode,
+            format,
+        );
+        assert!(cigar.is_none());
+        // NW : flanking insertions are kept, as in edlib cigar
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_NW;
+        let query = "GGACGTAAAATT";
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let cigar = alignment_to_cigar_with_clips(
+            query.as_bytes(),
+            target.as_bytes(),
+            &align_res,
+            config.mode,
+            format,
+        );
+        assert_eq!(cigar, Some(String::from("2I8=2I")));
+        assert_eq!(
+            cigar.unwrap(),
+            edlibAlignmentToCigarRs(align_res.alignment().unwrap(), &format)
+        );
+    } // end of test_cigar_with_clips
+
+    #[test]
+    fn test_write_alignment() {
+        let query = "missing";
+        let target = "xxmississipi";
+        //
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let mut out = Vec::<u8>::new();
+        write_alignment(&mut out, query.as_bytes(), target.as_bytes(), &align_res, 4).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let expected =
+            "Q  0 miss 3\n     ||||\nT  2 miss 5\n\nQ  4 ing 6\n     |  \nT  6 i-- 6\n\n";
+        assert_eq!(text, expected);
+        // no path
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_DISTANCE;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let mut out = Vec::<u8>::new();
+        let res = write_alignment(&mut out, query.as_bytes(), target.as_bytes(), &align_res, 4);
+        assert!(res.is_err());
+        // sequences not the ones aligned
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let res = write_alignment(&mut out, b"miss", target.as_bytes(), &align_res, 4);
+        assert_eq!(res.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        let res = write_alignment(&mut out, query.as_bytes(), b"xxmis", &align_res, 4);
+        assert_eq!(res.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    } // end of test_write_alignment
+
+    #[test]
+    fn test_variants() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        // one mismatch
+        let query = "ACGTTCGT";
+        let target = "ACGTACGT";
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let variants =
+            alignment_to_variants(query.as_bytes(), target.as_bytes(), &align_res, 0).unwrap();
+        assert_eq!(
+            variants,
+            vec![Variant {
+                pos: 4,
+                ref_allele: String::from("A"),
+                alt_allele: String::from("T"),
+            }]
+        );
+        let variants =
+            alignment_to_variants(query.as_bytes(), target.as_bytes(), &align_res, 100).unwrap();
+        assert_eq!(variants[0].pos, 104);
+        // deletion of GG after C, insertion of T after the last A
+        let query = "AACTTAAT";
+        let target = "AACGGTTAA";
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.editDistance, 3);
+        let variants =
+            alignment_to_variants(query.as_bytes(), target.as_bytes(), &align_res, 0).unwrap();
+        assert_eq!(variants.len(), 2);
+        assert_eq!(
+            (
+                variants[0].pos,
+                variants[0].ref_allele.as_str(),
+                variants[0].alt_allele.as_str()
+            ),
+            (2, "CGG", "C")
+        );
+        assert_eq!(
+            (
+                variants[1].pos,
+                variants[1].ref_allele.as_str(),
+                variants[1].alt_allele.as_str()
+            ),
+            (8, "A", "AT")
+        );
+        // insertion at reference start is anchored on the next base
+        let query = "GACGT";
+        let target = "ACGT";
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let variants =
+            alignment_to_variants(query.as_bytes(), target.as_bytes(), &align_res, 0).unwrap();
+        assert_eq!(
+            variants,
+            vec![Variant {
+                pos: 0,
+                ref_allele: String::from("A"),
+                alt_allele: String::from("GA"),
+            }]
+        );
+        // no path
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert!(
+            alignment_to_variants(query.as_bytes(), target.as_bytes(), &align_res, 0).is_none()
+        );
+    } // end of test_variants
+
+    #[test]
+    fn test_dotplot_points() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let query = b"missing";
+        let target = b"mississipi";
+        let align_res = edlibAlignRs(query, target, &config);
+        let points = dotplot_points(query, target, &align_res).unwrap();
+        assert_eq!(points.len(), count_matches(align_res.alignment().unwrap()));
+        assert_eq!(points, vec![(0, 0), (1, 1), (2, 2), (3, 3), (4, 4)]);
+        assert!(points
+            .windows(2)
+            .all(|w| w[0].0 < w[1].0 && w[0].1 < w[1].1));
+        for &(q, t) in &points {
+            assert_eq!(query[q], target[t]);
+        }
+        // target too short
+        assert!(dotplot_points(query, b"mis", &align_res).is_none());
+        // no path
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(query, target, &config);
+        assert!(dotplot_points(query, target, &align_res).is_none());
+    } // end of test_dotplot_points
+} // mod tests
@@ -5,13 +5,44 @@
 
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
-#![allow(non_snake_case)]
 
+#[allow(non_snake_case)]
 pub mod edlib_sys {
     include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
 }
 
+pub mod batch;
+#[cfg(feature = "bio-types")]
+pub mod bioalign;
+pub mod cigar;
+pub mod distance;
+pub mod equalities;
+#[cfg(feature = "fasta")]
+pub mod fasta;
+pub mod formats;
+pub mod path;
+pub mod prepared;
+pub mod raw;
+pub mod reference;
+pub mod scratch;
+pub use batch::*;
+#[cfg(feature = "bio-types")]
+pub use bioalign::*;
+pub use cigar::*;
+pub use distance::*;
+pub use equalities::*;
+#[cfg(feature = "fasta")]
+pub use fasta::*;
+pub use formats::*;
+pub use path::*;
+pub use prepared::*;
+pub use raw::*;
+pub use reference::*;
+pub use scratch::*;
+
 use edlib_sys::*;
+use std::convert::TryFrom;
+use std::fmt;
 use std::os::raw::c_char;
 use std::slice;
 
@@ -20,17 +51,24 @@ pub const EDLIB_RS_STATUS_OK: u32 = 0;
 #[allow(dead_code)]
 pub const EDLIB_RS_STATUS_ERROR: u32 = 1;
 
+/// Version of the edlib C++ library the crate was built against, as declared in edlib's CMakeLists.txt (e.g "1.2.6").
+/// Useful in bug reports.
+pub fn edlib_version() -> &'static str {
+    env!("EDLIB_VERSION")
+}
+
 ///
 /// Alignment methods - how should Edlib treat gaps before and after query?
 ///
 #[derive(Debug, Copy, Clone)]
 #[repr(C)]
+#[non_exhaustive]
 pub enum EdlibAlignModeRs {
     ///
     /// Global method. This is the standard and default method.
     /// Useful when you want to find out how similar is first sequence to second sequence.
     ///
-    EDLIB_MODE_NW,
+    EDLIB_MODE_NW = EdlibAlignMode_EDLIB_MODE_NW as isize,
 
     /// Prefix method. Similar to global method, but with a small twist - gap at query end is not penalized.
     /// What that means is that deleting elements from the end of second sequence is "free"!
@@ -38,7 +76,7 @@ pub enum EdlibAlignModeRs {
     /// of second sequence is "free" and does not count into total edit distance.
     /// This method is appropriate when you want to find out how well first sequence fits at the beginning of second sequence.
     ///
-    EDLIB_MODE_SHW,
+    EDLIB_MODE_SHW = EdlibAlignMode_EDLIB_MODE_SHW as isize,
 
     /// Infix method. Similar as prefix method, but with one more twist - gaps at query end and start are
     /// not penalized. What that means is that deleting elements from the start and end of second sequence is "free"!
@@ -51,7 +89,7 @@ pub enum EdlibAlignModeRs {
     /// that text.
     /// In bioinformatics, this method is appropriate for aligning read to a sequence.
     ///
-    EDLIB_MODE_HW,
+    EDLIB_MODE_HW = EdlibAlignMode_EDLIB_MODE_HW as isize,
 }
 
 ///
@@ -59,13 +97,14 @@ pub enum EdlibAlignModeRs {
 ///
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
 pub enum EdlibAlignTaskRs {
     /// Find edit distance and end locations. This is the default mode.
-    EDLIB_TASK_DISTANCE,
+    EDLIB_TASK_DISTANCE = EdlibAlignTask_EDLIB_TASK_DISTANCE as isize,
     ///  Find edit distance, end locations and start locations.
-    EDLIB_TASK_LOC,
+    EDLIB_TASK_LOC = EdlibAlignTask_EDLIB_TASK_LOC as isize,
     /// Find edit distance, end locations and start locations and alignment path.
-    EDLIB_TASK_PATH,
+    EDLIB_TASK_PATH = EdlibAlignTask_EDLIB_TASK_PATH as isize,
 }
 
 ///
@@ -75,29 +114,96 @@ pub enum EdlibAlignTaskRs {
 ///
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
 pub enum EdlibCigarFormatRs {
     /// Match: 'M', Insertion: 'I', Deletion: 'D', Mismatch: 'M'.
-    EDLIB_CIGAR_STANDARD,
+    EDLIB_CIGAR_STANDARD = EdlibCigarFormat_EDLIB_CIGAR_STANDARD as isize,
     ///    Match: '=', Insertion: 'I', Deletion: 'D', Mismatch: 'X'.
-    EDLIB_CIGAR_EXTENDED,
+    EDLIB_CIGAR_EXTENDED = EdlibCigarFormat_EDLIB_CIGAR_EXTENDED as isize,
 }
 
 /// Edit operations.
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
+#[non_exhaustive]
 pub enum EdlibEdopRs {
     /// Match
-    EDLIB_EDOP_MATCH,
+    EDLIB_EDOP_MATCH = edlib_sys::EDLIB_EDOP_MATCH as isize,
     /// Insertion to target = deletion from query
-    EDLIB_EDOP_INSERT,
+    EDLIB_EDOP_INSERT = edlib_sys::EDLIB_EDOP_INSERT as isize,
     /// Deletion from target = insertion to query.
-    EDLIB_EDOP_DELETE,
+    EDLIB_EDOP_DELETE = edlib_sys::EDLIB_EDOP_DELETE as isize,
     /// Mismatch.
-    EDLIB_EDOP_MISMATCH,
+    EDLIB_EDOP_MISMATCH = edlib_sys::EDLIB_EDOP_MISMATCH as isize,
+}
+
+/// Error returned when converting an integer that is not a known value of an edlib enum.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EdlibEnumError {
+    /// name of the enum type
+    pub name: &'static str,
+    /// the unknown value
+    pub value: u32,
+}
+
+impl fmt::Display for EdlibEnumError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown value {} for {}", self.value, self.name)
+    }
 }
 
+impl std::error::Error for EdlibEnumError {}
+
+// Conversion from the raw integers of edlib C enums and defines.
+macro_rules! impl_try_from_u32 {
+    ($rs_enum:ident, $($variant:ident),+) => {
+        impl TryFrom<u32> for $rs_enum {
+            type Error = EdlibEnumError;
+
+            fn try_from(value: u32) -> Result<Self, Self::Error> {
+                $(
+                    if value == $rs_enum::$variant as u32 {
+                        return Ok($rs_enum::$variant);
+                    }
+                )+
+                Err(EdlibEnumError {
+                    name: stringify!($rs_enum),
+                    value,
+                })
+            }
+        }
+    };
+}
+
+impl_try_from_u32!(
+    EdlibAlignModeRs,
+    EDLIB_MODE_NW,
+    EDLIB_MODE_SHW,
+    EDLIB_MODE_HW
+);
+impl_try_from_u32!(
+    EdlibAlignTaskRs,
+    EDLIB_TASK_DISTANCE,
+    EDLIB_TASK_LOC,
+    EDLIB_TASK_PATH
+);
+impl_try_from_u32!(
+    EdlibCigarFormatRs,
+    EDLIB_CIGAR_STANDARD,
+    EDLIB_CIGAR_EXTENDED
+);
+impl_try_from_u32!(
+    EdlibEdopRs,
+    EDLIB_EDOP_MATCH,
+    EDLIB_EDOP_INSERT,
+    EDLIB_EDOP_DELETE,
+    EDLIB_EDOP_MISMATCH
+);
+
 // We use c_char here to be able to cast C pointer directly
 /// Defines two given characters as equal.
+/// c_char is i8 on x86 and x86_64 but u8 on ARM/AArch64 : build pairs with new (or from_chars)
+/// and read them with first and second, which convert bytes to and from c_char the same way on all platforms.
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct EdlibEqualityPairRs {
@@ -105,6 +211,40 @@ pub struct EdlibEqualityPairRs {
     pub second: ::std::os::raw::c_char,
 }
 
+impl EdlibEqualityPairRs {
+    /// Builds a pair declaring bytes a and b equal. Any byte value can be used, including bytes >= 128.
+    /// The bytes are stored as c_char whose signedness varies by platform, the cast keeps the bit pattern
+    /// (200u8 is stored as -56 where c_char is i8) and edlib compares the pair with the sequence bytes,
+    /// themselves passed as c_char with the same cast, as chars without sign extension. So a pair
+    /// matches the same bytes on signed and unsigned char platforms.
+    pub fn new(a: u8, b: u8) -> Self {
+        EdlibEqualityPairRs {
+            first: a as c_char,
+            second: b as c_char,
+        }
+    }
+
+    /// Builds a pair declaring chars a and b equal.
+    /// Panics if a char is not ASCII, as edlib works on bytes.
+    pub fn from_chars(a: char, b: char) -> Self {
+        assert!(
+            a.is_ascii() && b.is_ascii(),
+            "non ASCII char in equality pair"
+        );
+        EdlibEqualityPairRs::new(a as u8, b as u8)
+    }
+
+    /// returns the first byte of the pair, as given to new whatever the signedness of c_char
+    pub fn first(&self) -> u8 {
+        self.first as u8
+    }
+
+    /// returns the second byte of the pair, as given to new whatever the signedness of c_char
+    pub fn second(&self) -> u8 {
+        self.second as u8
+    }
+}
+
 //=================================================================================================
 ///
 /// Configuration object for edlibAlign() function.
@@ -156,6 +296,110 @@ impl<'a> EdlibAlignConfigRs<'a> {
             additionalequalities,
         }
     }
+
+    /// Default configuration for global alignment (EDLIB_MODE_NW, Needleman-Wunsch).
+    /// k = -1, task = EDLIB_TASK_DISTANCE, no additional equalities.
+    ///
+    /// ```
+    /// use edlib_rs::*;
+    /// let align_res = edlibAlignRs(b"ACCTCTG", b"ACTCTGAAA", &EdlibAlignConfigRs::global());
+    /// assert_eq!(align_res.editDistance, 4);
+    /// ```
+    pub fn global() -> Self {
+        EdlibAlignConfigRs::default()
+    }
+
+    /// Default configuration for global alignment with a bound k on the edit distance :
+    /// edlib stops as soon as distance is known to exceed k and sets editDistance to -1.
+    /// With the default k = -1 edlib auto-adjusts k, doubling it until a score is found, so the computation
+    /// is not bounded and can be slow on distant sequences. A bounded configuration guarantees the work done
+    /// is at most proportional to k and the sequence lengths.
+    /// task = EDLIB_TASK_DISTANCE, no additional equalities.
+    ///
+    /// In debug builds, **panics** if k is negative.
+    ///
+    /// ```
+    /// use edlib_rs::*;
+    /// let config = EdlibAlignConfigRs::bounded(3);
+    /// assert!(config.is_bounded());
+    /// assert_eq!(edlibAlignRs(b"ACCTCTG", b"ACTCTGAAA", &config).editDistance, -1);
+    /// let config = EdlibAlignConfigRs::bounded(10);
+    /// assert_eq!(edlibAlignRs(b"ACCTCTG", b"ACTCTGAAA", &config).editDistance, 4);
+    /// ```
+    pub fn bounded(k: i32) -> Self {
+        debug_assert!(k >= 0, "bounded configuration with negative k {}", k);
+        EdlibAlignConfigRs {
+            k,
+            ..Default::default()
+        }
+    }
+
+    /// true if k is non negative, i.e edlib does not auto-adjust k and gives up above k.
+    pub fn is_bounded(&self) -> bool {
+        self.k >= 0
+    }
+
+    /// Default configuration for prefix alignment (EDLIB_MODE_SHW). Gap after query is not penalized.
+    /// k = -1, task = EDLIB_TASK_DISTANCE, no additional equalities.
+    ///
+    /// ```
+    /// use edlib_rs::*;
+    /// let align_res = edlibAlignRs(b"ACCTCTG", b"ACTCTGAAA", &EdlibAlignConfigRs::prefix());
+    /// assert_eq!(align_res.editDistance, 1);
+    /// ```
+    pub fn prefix() -> Self {
+        EdlibAlignConfigRs {
+            mode: EdlibAlignModeRs::EDLIB_MODE_SHW,
+            ..Default::default()
+        }
+    }
+
+    /// Default configuration for infix alignment (EDLIB_MODE_HW). Gaps before and after query are not penalized.
+    /// k = -1, task = EDLIB_TASK_DISTANCE, no additional equalities.
+    ///
+    /// ```
+    /// use edlib_rs::*;
+    /// let align_res = edlibAlignRs(b"ACCTCTG", b"TTTTTACTCTGAAA", &EdlibAlignConfigRs::infix());
+    /// assert_eq!(align_res.editDistance, 1);
+    /// ```
+    pub fn infix() -> Self {
+        EdlibAlignConfigRs {
+            mode: EdlibAlignModeRs::EDLIB_MODE_HW,
+            ..Default::default()
+        }
+    }
+
+    /// Default configuration (global mode) with the given equalities, typically obtained from
+    /// ascii_case_insensitive_equalities().
+    /// As the config only borrows the equalities, the caller must keep the Vec alive while the config is used:
+    ///
+    /// ```
+    /// use edlib_rs::*;
+    /// let equalities = ascii_case_insensitive_equalities();
+    /// let config = EdlibAlignConfigRs::case_insensitive(&equalities);
+    /// let align_res = edlibAlignRs(b"Hello", b"hELLO", &config);
+    /// assert_eq!(align_res.editDistance, 0);
+    /// ```
+    pub fn case_insensitive(equalities: &'a [EdlibEqualityPairRs]) -> Self {
+        EdlibAlignConfigRs {
+            additionalequalities: equalities,
+            ..Default::default()
+        }
+    }
+
+    /// Returns an owned copy of self with an equality pair added for each (char, char) of pairs,
+    /// after the equalities self already has.
+    ///
+    /// **Panics** if a char is not ASCII, see EdlibEqualityPairRs::from_chars.
+    pub fn with_char_equalities(self, pairs: &[(char, char)]) -> EdlibAlignConfigOwned {
+        let mut config = EdlibAlignConfigOwned::from(&self);
+        config.additionalequalities.extend(
+            pairs
+                .iter()
+                .map(|&(a, b)| EdlibEqualityPairRs::from_chars(a, b)),
+        );
+        config
+    }
 }
 
 impl<'a> Default for EdlibAlignConfigRs<'a> {
@@ -170,10 +414,83 @@ impl<'a> Default for EdlibAlignConfigRs<'a> {
     }
 }
 
+/// Configuration owning its equalities, so it can be stored without lifetime (in a struct for example).
+/// Get an EdlibAlignConfigRs for alignment with as_borrowed.
+#[derive(Debug, Clone)]
+pub struct EdlibAlignConfigOwned {
+    /// see EdlibAlignConfigRs.k
+    pub k: i32,
+    /// see EdlibAlignConfigRs.mode
+    pub mode: EdlibAlignModeRs,
+    /// see EdlibAlignConfigRs.task
+    pub task: EdlibAlignTaskRs,
+    /// see EdlibAlignConfigRs.additionalequalities
+    pub additionalequalities: Vec<EdlibEqualityPairRs>,
+}
+
+impl EdlibAlignConfigOwned {
+    /// returns the configuration borrowing self equalities, to pass to alignment functions.
+    pub fn as_borrowed(&self) -> EdlibAlignConfigRs<'_> {
+        EdlibAlignConfigRs::new(self.k, self.mode, self.task, &self.additionalequalities)
+    }
+
+    /// Returns self with its equalities replaced by normalize_equalities of them :
+    /// each pair present in both directions, without duplicates.
+    pub fn with_normalized_equalities(mut self) -> Self {
+        self.additionalequalities = normalize_equalities(&self.additionalequalities);
+        self
+    }
+
+    /// Returns self with the equalities between soft-masked (lowercase) nucleotides and their uppercase form
+    /// added, i.e a/A, c/C, g/G, t/T and n/N, after the equalities self already has.
+    /// This only covers nucleotide masking of genomic references, other letters stay case sensitive :
+    /// see EdlibAlignConfigRs::case_insensitive and equalities::ascii_case_insensitive_equalities
+    /// for general case insensitivity.
+    pub fn with_softmask_insensitive(mut self) -> Self {
+        self.additionalequalities.extend(
+            b"ACGTN"
+                .iter()
+                .map(|&c| EdlibEqualityPairRs::new(c.to_ascii_lowercase(), c)),
+        );
+        self
+    }
+}
+
+impl Default for EdlibAlignConfigOwned {
+    ///      k = -1, mode = EDLIB_MODE_NW, task = EDLIB_TASK_DISTANCE, no additional equalities.
+    fn default() -> Self {
+        EdlibAlignConfigOwned::from(&EdlibAlignConfigRs::default())
+    }
+}
+
+impl<'a> From<&EdlibAlignConfigRs<'a>> for EdlibAlignConfigOwned {
+    fn from(config: &EdlibAlignConfigRs<'a>) -> Self {
+        EdlibAlignConfigOwned {
+            k: config.k,
+            mode: config.mode,
+            task: config.task,
+            additionalequalities: config.additionalequalities.to_vec(),
+        }
+    }
+}
+
 //================================================================================================
 
+/// Outcome of an alignment, combining status and editDistance of EdlibAlignResultRs, see outcome().
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AlignOutcome {
+    /// alignment done, with its edit distance.
+    Found(u32),
+    /// alignment done but edit distance is larger than the k requested (editDistance is -1).
+    ExceededK,
+    /// edlib returned status EDLIB_STATUS_ERROR.
+    Error,
+}
+
 /// Container for results of alignment done by edlibAlign() function.
+/// Fields keep the camelCase names of edlib for source compatibility, see the snake_case accessors.
 #[derive(Debug, Clone)]
+#[allow(non_snake_case)]
 pub struct EdlibAlignResultRs {
     /// EDLIB_STATUS_OK or EDLIB_STATUS_ERROR. If error, all other fields will have undefined values.
     pub status: u32,
@@ -211,22 +528,366 @@ pub struct EdlibAlignResultRs {
 } // end of struct EdlibAlignResultRs
 
 impl EdlibAlignResultRs {
+    /// get result distance, -1 if distance is greater than the k requested in the configuration.
+    pub fn edit_distance(&self) -> i32 {
+        self.editDistance
+    }
+    /// get result status, EDLIB_STATUS_OK or EDLIB_STATUS_ERROR.
+    pub fn status(&self) -> u32 {
+        self.status
+    }
+    /// get number of distinct characters in query and target, see compute_alphabet.
+    pub fn alphabet_length(&self) -> u32 {
+        self.alphabetLength
+    }
+    /// get end locations of optimal alignment path (0-based)
+    pub fn end_locations(&self) -> Option<&[i32]> {
+        self.endLocations.as_deref()
+    }
+    /// get start locations of optimal alignment path (0-based)
+    pub fn start_locations(&self) -> Option<&[i32]> {
+        self.startLocations.as_deref()
+    }
+    /// get alignment path, see path module for opcodes
+    pub fn alignment(&self) -> Option<&[u8]> {
+        self.alignment.as_deref()
+    }
     /// get result distance
+    #[deprecated(since = "0.3.0", note = "use edit_distance")]
+    #[allow(non_snake_case)]
     pub fn getDistance(&self) -> i32 {
         return self.editDistance;
     }
+    /// get result distance, None if distance is greater than the k requested in the configuration
+    /// (editDistance is then -1).
+    pub fn distance(&self) -> Option<u32> {
+        if self.editDistance < 0 {
+            None
+        } else {
+            Some(self.editDistance as u32)
+        }
+    }
+    /// get the outcome of the alignment : Error if status is not EDLIB_STATUS_OK, ExceededK if editDistance is -1,
+    /// Found with the distance otherwise.
+    pub fn outcome(&self) -> AlignOutcome {
+        if self.status != EDLIB_STATUS_OK {
+            AlignOutcome::Error
+        } else if self.editDistance < 0 {
+            AlignOutcome::ExceededK
+        } else {
+            AlignOutcome::Found(self.editDistance as u32)
+        }
+    }
     /// get end locations of optimal alignment path
+    #[deprecated(since = "0.3.0", note = "use end_locations")]
+    #[allow(non_snake_case)]
     pub fn getEndLocations(&self) -> Option<&Vec<i32>> {
         return self.endLocations.as_ref();
     }
     /// get start locations of optimal alignment path
+    #[deprecated(since = "0.3.0", note = "use start_locations")]
+    #[allow(non_snake_case)]
     pub fn getStartLocations(&self) -> Option<&Vec<i32>> {
         return self.startLocations.as_ref();
     }
+    /// get end locations as 1-based positions (as in SAM or VCF), i.e endLocations + 1.
+    /// The field endLocations and end_locations stay 0-based as in edlib.
+    pub fn end_locations_1based(&self) -> Option<Vec<i32>> {
+        self.endLocations
+            .as_ref()
+            .map(|ends| ends.iter().map(|end| end + 1).collect())
+    }
+    /// get start locations as 1-based positions (as in SAM or VCF), i.e startLocations + 1.
+    /// The field startLocations and start_locations stay 0-based as in edlib.
+    pub fn start_locations_1based(&self) -> Option<Vec<i32>> {
+        self.startLocations
+            .as_ref()
+            .map(|starts| starts.iter().map(|start| start + 1).collect())
+    }
     ///
+    #[deprecated(since = "0.3.0", note = "use alignment")]
+    #[allow(non_snake_case)]
     pub fn getAlignment(&self) -> Option<&Vec<u8>> {
         return self.alignment.as_ref();
     }
+
+    /// get number of end (and start) locations.
+    pub fn num_locations(&self) -> usize {
+        self.numLocations
+    }
+
+    /// true if the alignment path was computed, i.e task was EDLIB_TASK_PATH and distance is within k.
+    pub fn has_path(&self) -> bool {
+        self.alignment.is_some()
+    }
+
+    /// true if both start and end locations were computed, i.e task was EDLIB_TASK_LOC or EDLIB_TASK_PATH
+    /// and distance is within k. End locations alone are also available with EDLIB_TASK_DISTANCE.
+    pub fn has_locations(&self) -> bool {
+        self.startLocations.is_some() && self.endLocations.is_some()
+    }
+
+    /// get (end, start) location pairs of optimal alignment paths.
+    /// There is one tuple per location so the length equals numLocations.
+    /// The start location is Some only if start locations were computed (task EDLIB_TASK_LOC or EDLIB_TASK_PATH).
+    pub fn locations(&self) -> Vec<(i32, Option<i32>)> {
+        let ends = match &self.endLocations {
+            Some(ends) => ends,
+            None => return Vec::new(),
+        };
+        match &self.startLocations {
+            Some(starts) => ends
+                .iter()
+                .zip(starts.iter())
+                .map(|(&end, &start)| (end, Some(start)))
+                .collect(),
+            None => ends.iter().map(|&end| (end, None)).collect(),
+        }
+    }
+
+    /// get the first optimal location as (end, start, distance).
+    /// edlib reports locations by increasing end position, all at the optimal distance, so index 0 is the optimal
+    /// hit ending first in target, and the one whose alignment path is computed with task EDLIB_TASK_PATH.
+    /// The start is Some only if start locations were computed (task EDLIB_TASK_LOC or EDLIB_TASK_PATH).
+    /// Returns None if there is no location or distance is -1 (larger than k).
+    pub fn best_location(&self) -> Option<(i32, Option<i32>, i32)> {
+        if self.editDistance < 0 {
+            return None;
+        }
+        let end = *self.endLocations.as_ref()?.first()?;
+        let start = self
+            .startLocations
+            .as_ref()
+            .and_then(|starts| starts.first().copied());
+        Some((end, start, self.editDistance))
+    }
+
+    /// get for each column of the alignment path the pair (query index, target index) it aligns,
+    /// with None on the gapped side.
+    /// The walk starts at query_start in query and at startLocations\[0\] in target.
+    /// Opcodes advance positions as follows :
+    ///     * 0 (match) and 3 (mismatch) advance both query and target.
+    ///     * 1 (insertion to target) advances query only, target index is None.
+    ///     * 2 (insertion to query) advances target only, query index is None.
+    ///
+    /// Returns None if there is no alignment path (task was not EDLIB_TASK_PATH) or query_start is negative.
+    pub fn coordinate_pairs(
+        &self,
+        query_start: i32,
+    ) -> Option<Vec<(Option<usize>, Option<usize>)>> {
+        if query_start < 0 {
+            return None;
+        }
+        let alignment = self.alignment()?;
+        let mut target_pos = *self.start_locations()?.first()? as usize;
+        let mut query_pos = query_start as usize;
+        let mut pairs = Vec::with_capacity(alignment.len());
+        for &op in alignment {
+            match op {
+                1 => {
+                    pairs.push((Some(query_pos), None));
+                    query_pos += 1;
+                }
+                2 => {
+                    pairs.push((None, Some(target_pos)));
+                    target_pos += 1;
+                }
+                _ => {
+                    pairs.push((Some(query_pos), Some(target_pos)));
+                    query_pos += 1;
+                    target_pos += 1;
+                }
+            }
+        }
+        Some(pairs)
+    }
+
+    /// get gap statistics of the alignment path as (total gap positions, gap open events).
+    /// Insertions to target (1) and insertions to query (2) both count as gaps, though they are gaps
+    /// in opposite sequences. A gap open event is the start of a run of identical gap opcodes,
+    /// so an insertion directly followed by a deletion counts as 2 opens.
+    /// Returns None if there is no alignment path (task was not EDLIB_TASK_PATH).
+    pub fn gap_stats(&self) -> Option<(usize, usize)> {
+        let alignment = self.alignment()?;
+        let mut gaps = 0;
+        let mut opens = 0;
+        let mut previous: Option<u8> = None;
+        for &op in alignment {
+            if op == 1 || op == 2 {
+                gaps += 1;
+                if previous != Some(op) {
+                    opens += 1;
+                }
+            }
+            previous = Some(op);
+        }
+        Some((gaps, opens))
+    }
+
+    /// get the longest run of consecutive matches (opcode 0) of the alignment path, as
+    /// (index of its first opcode in alignment, length). If several runs have the longest length
+    /// the leftmost one is returned. A path without any match gives (0, 0).
+    /// Returns None if there is no alignment path (task was not EDLIB_TASK_PATH).
+    pub fn longest_match_run(&self) -> Option<(usize, usize)> {
+        let alignment = self.alignment()?;
+        let mut best = (0, 0);
+        let mut run_start = 0;
+        for (i, &op) in alignment.iter().enumerate() {
+            if op != EDLIB_EDOP_MATCH as u8 {
+                run_start = i + 1;
+            } else if i + 1 - run_start > best.1 {
+                best = (run_start, i + 1 - run_start);
+            }
+        }
+        Some(best)
+    }
+
+    /// get the positions in target of the mismatches (opcode 3) of the alignment path, target positions
+    /// starting at startLocations\[0\]. ref_start is added to each position, it is the position of target
+    /// in a longer reference when target is a part of it (0 gives target positions), as in alignment_to_variants.
+    /// This parameter deliberately is not a query_start as in coordinate_pairs : positions are in target
+    /// coordinates, which a query offset would not change.
+    /// Returns None if there is no alignment path or start location, or if ref_start is negative.
+    pub fn mismatch_positions(&self, ref_start: i32) -> Option<Vec<usize>> {
+        if ref_start < 0 {
+            return None;
+        }
+        let alignment = self.alignment()?;
+        let mut target_pos = ref_start as usize + *self.start_locations()?.first()? as usize;
+        let mut positions = Vec::new();
+        for &op in alignment {
+            if op == EDLIB_EDOP_MISMATCH as u8 {
+                positions.push(target_pos);
+            }
+            if op != EDLIB_EDOP_INSERT as u8 {
+                target_pos += 1;
+            }
+        }
+        Some(positions)
+    }
+
+    /// get the identity over the aligned query : matches divided by the number of query positions consumed by
+    /// the alignment path (matches, mismatches and insertions). This differs from block identity, matches divided
+    /// by the alignment length including deletions (see paf_match_block and passes_identity_threshold) :
+    /// deletions (target only residues) lower block identity but not query identity.
+    /// query_len is the length of the aligned query, checked against the path.
+    /// Returns None if there is no alignment path (task was not EDLIB_TASK_PATH), or if the path does not consume
+    /// query_len query positions, or if query is empty.
+    pub fn query_identity(&self, query_len: usize) -> Option<f64> {
+        let alignment = self.alignment()?;
+        let consumed = alignment
+            .iter()
+            .filter(|&&op| op != EDLIB_EDOP_DELETE as u8)
+            .count();
+        if consumed != query_len || consumed == 0 {
+            return None;
+        }
+        let (matches, _) = self.paf_match_block()?;
+        Some(matches as f64 / consumed as f64)
+    }
+
+    /// get the PAF columns 10 and 11 as (number of residue matches, alignment block length) :
+    /// matches are the match opcodes (0), the block length is the number of opcodes including mismatches and gaps.
+    /// Returns None if there is no alignment path (task was not EDLIB_TASK_PATH).
+    pub fn paf_match_block(&self) -> Option<(usize, usize)> {
+        let alignment = self.alignment()?;
+        let matches = alignment
+            .iter()
+            .filter(|&&op| op == EDLIB_EDOP_MATCH as u8)
+            .count();
+        Some((matches, alignment.len()))
+    }
+
+    /// get the part of target aligned to query for the first location, i.e target\[start..=end\]
+    /// with start = startLocations\[0\] and end = endLocations\[0\].
+    /// Returns None if start locations were not computed (task EDLIB_TASK_DISTANCE) or if locations
+    /// are out of target range (target is not the sequence that was aligned).
+    pub fn aligned_target_slice<'t>(&self, target: &'t [u8]) -> Option<&'t [u8]> {
+        let start = *self.start_locations()?.first()?;
+        let end = *self.end_locations()?.first()?;
+        if start < 0 || end < start || end as usize >= target.len() {
+            return None;
+        }
+        Some(&target[start as usize..=end as usize])
+    }
+
+    /// get the range of target aligned to query for the first location, startLocations\[0\]..endLocations\[0\] + 1,
+    /// so that &target\[result.target_range()?\] is the aligned infix.
+    /// Contrary to aligned_target_slice the range is not checked against a target length.
+    /// Returns None if start locations were not computed (task EDLIB_TASK_DISTANCE).
+    pub fn target_range(&self) -> Option<std::ops::Range<usize>> {
+        let start = *self.start_locations()?.first()?;
+        let end = *self.end_locations()?.first()?;
+        if start < 0 || end + 1 < start {
+            return None;
+        }
+        Some(start as usize..(end + 1) as usize)
+    }
+
+    /// get the range of query that is aligned. edlib aligns the whole query in all modes (NW, SHW and HW),
+    /// only target flanks can be free, so this is always 0..query.len(). As the query length is not stored
+    /// in the result, it is recovered from the alignment path by counting matches, mismatches and insertions.
+    /// Returns None if there is no alignment path (task was not EDLIB_TASK_PATH).
+    pub fn query_range(&self) -> Option<std::ops::Range<usize>> {
+        let alignment = self.alignment()?;
+        let query_len = alignment
+            .iter()
+            .filter(|&&op| op != EDLIB_EDOP_DELETE as u8)
+            .count();
+        Some(0..query_len)
+    }
+
+    /// get edit distance divided by the length of the longer sequence, a value in \[0,1\] comparable
+    /// between pairs of different lengths.
+    /// Returns None if distance is greater than the requested k (editDistance is -1).
+    /// In HW and SHW modes target flanks are free and normalized_by_query is usually more appropriate.
+    pub fn normalized_distance(&self, query_len: usize, target_len: usize) -> Option<f64> {
+        let distance = self.distance()?;
+        let len = query_len.max(target_len);
+        if len == 0 {
+            return Some(0.);
+        }
+        Some(distance as f64 / len as f64)
+    }
+
+    /// get edit distance divided by query length. This is the natural normalization in HW and SHW modes
+    /// where only the query is aligned entirely.
+    /// Returns None if distance is greater than the requested k (editDistance is -1).
+    pub fn normalized_by_query(&self, query_len: usize) -> Option<f64> {
+        let distance = self.distance()?;
+        if query_len == 0 {
+            return Some(0.);
+        }
+        Some(distance as f64 / query_len as f64)
+    }
+
+    /// Converts a raw C result, as returned by edlib_sys::edlibAlign called directly, to an EdlibAlignResultRs.
+    /// Locations and alignment are copied with the same null checks as edlibAlignRs : a null pointer
+    /// with a positive length gives status EDLIB_STATUS_ERROR.
+    ///
+    /// # Safety
+    /// res must come from edlib (or have null pointers or pointers valid for numLocations locations and
+    /// alignmentLength opcodes). It is not freed : the caller keeps the responsibility to call
+    /// edlib_sys::edlibFreeAlignResult on it.
+    pub unsafe fn from_raw(res: &EdlibAlignResult) -> Self {
+        let mut align_res = EdlibAlignResultRs::default();
+        copyFromC(&mut align_res, res);
+        align_res
+    }
+
+    /// Gives up ownership of end locations, start locations and alignment as boxed slices, without copy,
+    /// to pass them through another FFI boundary. A field that is None gives an empty slice.
+    /// Each slice can be turned into a pointer and a length for C with Box::into_raw (or as_ptr and len).
+    /// The memory is allocated by Rust, not by malloc : it must not be released with free() on the C side
+    /// but given back to Rust and rebuilt with Box::from_raw, which drops it.
+    #[allow(clippy::type_complexity)]
+    pub fn into_c_arrays(self) -> (Box<[i32]>, Box<[i32]>, Box<[u8]>) {
+        (
+            self.endLocations.unwrap_or_default().into_boxed_slice(),
+            self.startLocations.unwrap_or_default().into_boxed_slice(),
+            self.alignment.unwrap_or_default().into_boxed_slice(),
+        )
+    }
 } // end EdlibAlignResultRs block
 
 impl Default for EdlibAlignResultRs {
@@ -244,6 +905,93 @@ impl Default for EdlibAlignResultRs {
     }
 } // end impl Default for EdlibAlignResultRs
 
+/// One line summary for debugging, as in dist=2 locs=1 \[0..4\] cigar=5M2I.
+/// The bracket gives startLocations\[0\] and endLocations\[0\] (inclusive), the start being omitted
+/// if not computed. The cigar, in standard format, is written only if the alignment path was computed.
+impl fmt::Display for EdlibAlignResultRs {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "dist={} locs={}", self.editDistance, self.numLocations)?;
+        if let Some(end) = self.endLocations.as_ref().and_then(|ends| ends.first()) {
+            match self
+                .startLocations
+                .as_ref()
+                .and_then(|starts| starts.first())
+            {
+                Some(start) => write!(f, " [{}..{}]", start, end)?,
+                None => write!(f, " [..{}]", end)?,
+            }
+        }
+        if let Some(alignment) = &self.alignment {
+            let cigar =
+                alignment_to_cigar_native(alignment, EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD);
+            write!(f, " cigar={}", cigar)?;
+        }
+        Ok(())
+    }
+} // end impl Display for EdlibAlignResultRs
+
+// Converts config to C. The returned config borrows config_rs equalities.
+#[allow(non_snake_case)]
+fn configToC(config_rs: &EdlibAlignConfigRs) -> EdlibAlignConfig {
+    // get pointers to query and target to EdlibEqualityPair form config
+    let mut config_c = unsafe { edlibDefaultAlignConfig() };
+    config_c.k = config_rs.k as ::std::os::raw::c_int;
+    // Rs enums discriminants are the bindgen constants of the C enums, so casts give the C values.
+    config_c.mode = config_rs.mode as EdlibAlignMode;
+    config_c.task = config_rs.task as EdlibAlignTask;
+    config_c.additionalEqualitiesLength =
+        config_rs.additionalequalities.len() as ::std::os::raw::c_int;
+    if config_c.additionalEqualitiesLength > 0 {
+        config_c.additionalEqualities =
+            config_rs.additionalequalities.as_ptr() as *const EdlibEqualityPair;
+    } else {
+        config_c.additionalEqualities = ::std::ptr::null::<EdlibEqualityPair>();
+    }
+    config_c
+}
+
+// Pointer to pass a sequence to C. An empty slice gives a null pointer instead of a dangling one,
+// edlib never reads it as length is 0.
+#[allow(non_snake_case)]
+fn seqToC(seq: &[u8]) -> *const ::std::os::raw::c_char {
+    if seq.is_empty() {
+        ::std::ptr::null()
+    } else {
+        seq.as_ptr() as *const ::std::os::raw::c_char
+    }
+}
+
+// Calls C edlibAlign. The result must be freed with edlibFreeAlignResult.
+#[allow(non_snake_case)]
+fn alignC(query: &[u8], target: &[u8], config_rs: &EdlibAlignConfigRs) -> EdlibAlignResult {
+    let config_c = configToC(config_rs);
+    unsafe {
+        edlibAlign(
+            seqToC(query),
+            query.len() as ::std::os::raw::c_int,
+            seqToC(target),
+            target.len() as ::std::os::raw::c_int,
+            // now config
+            config_c,
+        )
+    }
+}
+
+// Aligns and returns only status and edit distance, without copying locations and alignment.
+#[allow(non_snake_case)]
+pub(crate) fn alignDistanceOnly(
+    query: &[u8],
+    target: &[u8],
+    config_rs: &EdlibAlignConfigRs,
+) -> (u32, i32) {
+    let res_c = alignC(query, target, config_rs);
+    let res = (res_c.status as u32, res_c.editDistance);
+    unsafe {
+        edlibFreeAlignResult(res_c);
+    };
+    res
+}
+
 /// Aligns two sequences (query and target) using edit distance (levenshtein distance).
 /// Through config parameter, this function supports different alignment methods (global, prefix, infix),
 /// as well as different modes of search (tasks).
@@ -258,126 +1006,957 @@ impl Default for EdlibAlignResultRs {
 /// **Note**:
 ///  Rust interface causes cloning of start/end locations, ensures i32 representations of locations and so transfer
 /// memory responsability to Rust.
-
+/// edlibAlignBorrowed avoids the copy for read-only use.
+/// **Empty sequences**:
+///  An empty query or target is valid. In NW mode editDistance is the length of the other sequence and the end
+/// location is target.len() - 1 (-1 if target is empty). In HW and SHW modes editDistance is query.len() and the end
+/// location is -1, as no target residue is aligned. With tasks LOC and PATH the start location is 0 and the path is
+/// made of deletions (empty query, NW) or insertions (empty target); it is None if both sequences are empty.
+/// **Thread safety**:
+///  edlib keeps no global mutable state (all working memory is allocated per call), so edlibAlignRs can be called
+/// concurrently from several threads. Config and result types are plain data and are Send and Sync.
+/// **Logging**:
+///  With feature logging, each alignment (also through edlibAlignInto) emits a log::trace! line with query and target
+/// lengths, mode, task, k, and the resulting status, distance and number of locations.
+#[allow(non_snake_case)]
 pub fn edlibAlignRs(
     query: &[u8],
     target: &[u8],
     config_rs: &EdlibAlignConfigRs,
 ) -> EdlibAlignResultRs {
-    // real work here
-    // get pointers to query and target to EdlibEqualityPair form config
-    let mut config_c = unsafe { edlibDefaultAlignConfig() };
-    config_c.k = config_rs.k as ::std::os::raw::c_int;
-    config_c.mode = match config_rs.mode {
-        EdlibAlignModeRs::EDLIB_MODE_NW => 0 as EdlibAlignMode,
-        EdlibAlignModeRs::EDLIB_MODE_SHW => 1 as EdlibAlignMode,
-        EdlibAlignModeRs::EDLIB_MODE_HW => 2 as EdlibAlignMode,
-    };
-    config_c.task = match config_rs.task {
-        EdlibAlignTaskRs::EDLIB_TASK_DISTANCE => 0 as EdlibAlignTask,
-        EdlibAlignTaskRs::EDLIB_TASK_LOC => 1 as EdlibAlignTask,
-        EdlibAlignTaskRs::EDLIB_TASK_PATH => 2 as EdlibAlignTask,
-    };
-    config_c.additionalEqualitiesLength =
-        config_rs.additionalequalities.len() as ::std::os::raw::c_int;
-    if config_c.additionalEqualitiesLength > 0 {
-        config_c.additionalEqualities =
-            config_rs.additionalequalities.as_ptr() as *const EdlibEqualityPair;
-    } else {
-        config_c.additionalEqualities = ::std::ptr::null::<EdlibEqualityPair>();
+    let mut align_res_rs = EdlibAlignResultRs::default();
+    edlibAlignInto(query, target, config_rs, &mut align_res_rs);
+    align_res_rs
+}
+
+// Copies src in dst, reusing the Vec of dst if any.
+#[allow(non_snake_case)]
+fn copyInto<T: Copy>(dst: &mut Option<Vec<T>>, src: &[T]) {
+    match dst {
+        Some(v) => {
+            v.clear();
+            v.extend_from_slice(src);
+        }
+        None => *dst = Some(src.to_vec()),
     }
+}
 
-    // Recast to EdlibAlignResultRs
-    let res_c: EdlibAlignResult = unsafe {
-        edlibAlign(
-            query.as_ptr() as *const ::std::os::raw::c_char,
-            query.len() as ::std::os::raw::c_int,
-            target.as_ptr() as *const ::std::os::raw::c_char,
-            target.len() as ::std::os::raw::c_int,
-            // now config
-            config_c,
-        )
+// Slices of the arrays of a C result, None for a null pointer or a non positive length.
+pub(crate) struct SlicesFromC<'a> {
+    // status of the C result, EDLIB_STATUS_ERROR if a location or alignment pointer is null with a positive length
+    pub(crate) status: u32,
+    pub(crate) ends: Option<&'a [::std::os::raw::c_int]>,
+    // Some only if ends is, edlib computes start locations only with task LOC or PATH
+    pub(crate) starts: Option<&'a [::std::os::raw::c_int]>,
+    pub(crate) alignment: Option<&'a [u8]>,
+}
+
+// Checks the pointers of the C result res_c and gets its arrays as slices.
+// Null location or alignment pointers with a positive length should not happen, they are reported as
+// EDLIB_STATUS_ERROR rather than panicking.
+// Safety : pointers of res_c must be null or valid for numLocations (alignmentLength) elements.
+#[allow(non_snake_case)]
+pub(crate) unsafe fn slicesFromC(res_c: &EdlibAlignResult) -> SlicesFromC<'_> {
+    let mut slices = SlicesFromC {
+        status: res_c.status as u32,
+        ends: None,
+        starts: None,
+        alignment: None,
     };
-    // go back to EdlibAlignResultRs. Clone incurs some cost. Should go to impl From<EdlibAlignResult>
-    let mut align_res_rs = EdlibAlignResultRs::default();
-    align_res_rs.status = res_c.status as u32;
-    align_res_rs.editDistance = res_c.editDistance as i32;
-    align_res_rs.numLocations = res_c.numLocations as usize;
-    // get  ::std::os::raw::c_int slices for endLocations
     if res_c.numLocations > 0 {
-        assert!(res_c.endLocations != std::ptr::null_mut());
-        let s_end =
-            unsafe { slice::from_raw_parts(res_c.endLocations, res_c.numLocations as usize) };
-        assert_eq!(s_end.len(), align_res_rs.numLocations);
-        align_res_rs.endLocations = Some(s_end.to_vec());
-        // we have startLocations only if task == LOC or PATH so we must check
-        if res_c.startLocations != std::ptr::null_mut() {
-            let s_start: &[::std::os::raw::c_int] =
-                unsafe { slice::from_raw_parts(res_c.startLocations, res_c.numLocations as usize) };
-            assert_eq!(s_start.len(), align_res_rs.numLocations);
-            align_res_rs.startLocations = Some(s_start.to_vec());
+        let n = res_c.numLocations as usize;
+        if res_c.endLocations.is_null() {
+            slices.status = EDLIB_STATUS_ERROR;
+        } else {
+            slices.ends = Some(slice::from_raw_parts(res_c.endLocations, n));
+            if !res_c.startLocations.is_null() {
+                slices.starts = Some(slice::from_raw_parts(res_c.startLocations, n));
+            }
         }
     }
     if res_c.alignmentLength > 0 {
-        assert!(
-            res_c.alignment != std::ptr::null_mut(),
-            "null alignment pointer"
-        );
-        let s_align =
-            unsafe { slice::from_raw_parts(res_c.alignment, res_c.alignmentLength as usize) };
-        align_res_rs.alignment = Some(s_align.to_vec());
+        if res_c.alignment.is_null() {
+            slices.status = EDLIB_STATUS_ERROR;
+        } else {
+            let n = res_c.alignmentLength as usize;
+            slices.alignment = Some(slice::from_raw_parts(res_c.alignment, n));
+        }
     }
-    align_res_rs.alphabetLength = res_c.alphabetLength as u32;
-    // Free C datas
-    unsafe {
-        edlibFreeAlignResult(res_c);
-    };
-    //
-    align_res_rs
+    slices
 }
 
-extern "C" {
-    fn free(s: *const c_char);
+// Copies the C result res_c in out, reusing the Vecs of out.
+// Safety : as slicesFromC.
+#[allow(non_snake_case)]
+unsafe fn copyFromC(out: &mut EdlibAlignResultRs, res_c: &EdlibAlignResult) {
+    let slices = slicesFromC(res_c);
+    out.status = slices.status;
+    out.editDistance = res_c.editDistance as i32;
+    out.numLocations = slices.ends.map_or(0, |ends| ends.len());
+    match slices.ends {
+        Some(ends) => copyInto(&mut out.endLocations, ends),
+        None => out.endLocations = None,
+    }
+    match slices.starts {
+        Some(starts) => copyInto(&mut out.startLocations, starts),
+        None => out.startLocations = None,
+    }
+    match slices.alignment {
+        Some(alignment) => copyInto(&mut out.alignment, alignment),
+        None => out.alignment = None,
+    }
+    out.alphabetLength = res_c.alphabetLength as u32;
 }
 
-/// Builds cigar string from given alignment sequence.
-///  param : alignment  Alignment sequence.
-///  (is obtained from EdlibAlignResultRs.alignment which is a Some if EdlibAlignConfigRs.task is set to EdlibAlignTaskRs::EDLIB_TASK_PATH
-///  see *test_path_hw*)
-///     *  0 stands for match.
-///     *  1 stands for insertion to target.
-///     *  2 stands for insertion to query.
-///     *  3 stands for mismatch.
-///  param cigarFormat  Cigar will be returned in specified format.
-///
-///   return Cigar string where :
-///    * I stands for insertion.
+// edlib returns before computing start locations and path if a sequence is empty.
+// EmptySequencePath completes them : the alignment starts at 0 and, with task PATH, is made of deletions
+// of the aligned target part followed by insertions of the whole query, one of these being 0.
+pub(crate) struct EmptySequencePath {
+    pub(crate) deletions: usize,
+    pub(crate) insertions: usize,
+    // true with task PATH, the path is to be written
+    pub(crate) with_path: bool,
+}
+
+impl EmptySequencePath {
+    // Returns the completion of the result of aligning query against target with config, given its status
+    // and end locations, or None if there is nothing to complete (no empty sequence, error, task DISTANCE).
+    pub(crate) fn new(
+        query: &[u8],
+        target: &[u8],
+        config: &EdlibAlignConfigRs,
+        status: u32,
+        ends: &[i32],
+    ) -> Option<Self> {
+        if !(query.is_empty() || target.is_empty())
+            || status != EDLIB_STATUS_OK
+            || matches!(config.task, EdlibAlignTaskRs::EDLIB_TASK_DISTANCE)
+        {
+            return None;
+        }
+        let &end = ends.first()?;
+        Some(EmptySequencePath {
+            deletions: (end + 1) as usize,
+            insertions: query.len(),
+            with_path: matches!(config.task, EdlibAlignTaskRs::EDLIB_TASK_PATH),
+        })
+    }
+
+    // Writes the path in path, replacing its content.
+    pub(crate) fn write_path(&self, path: &mut Vec<u8>) {
+        path.clear();
+        path.resize(self.deletions, EDLIB_EDOP_DELETE as u8);
+        path.resize(self.deletions + self.insertions, EDLIB_EDOP_INSERT as u8);
+    }
+} // end impl EmptySequencePath
+
+/// Same as edlibAlignRs but writes the result in out, reusing the allocations of its Vecs.
+/// This amortizes allocations when processing many pairs in a loop with the same out.
+/// All fields of out are overwritten; a Vec is dropped only if the new result has None in this field.
+#[allow(non_snake_case)]
+pub fn edlibAlignInto(
+    query: &[u8],
+    target: &[u8],
+    config_rs: &EdlibAlignConfigRs,
+    out: &mut EdlibAlignResultRs,
+) {
+    // real work here
+    let res_c = alignC(query, target, config_rs);
+    // go back to EdlibAlignResultRs. Copy incurs some cost.
+    unsafe {
+        copyFromC(out, &res_c);
+    }
+    let ends = out.endLocations.as_deref().unwrap_or(&[]);
+    if let Some(empty) = EmptySequencePath::new(query, target, config_rs, out.status, ends) {
+        copyInto(&mut out.startLocations, &[0]);
+        if empty.with_path {
+            let mut path = out.alignment.take().unwrap_or_default();
+            empty.write_path(&mut path);
+            out.alignment = if path.is_empty() { None } else { Some(path) };
+        }
+    }
+    #[cfg(feature = "logging")]
+    log::trace!(
+        "edlib align query len {} target len {} mode {:?} task {:?} k {} : status {} distance {} numLocations {}",
+        query.len(),
+        target.len(),
+        config_rs.mode,
+        config_rs.task,
+        config_rs.k,
+        out.status,
+        out.editDistance,
+        out.numLocations
+    );
+    // Free C datas
+    unsafe {
+        edlibFreeAlignResult(res_c);
+    };
+}
+
+/// Aligns query and target with an outer retry loop on k : alignment is first run with k = initial_k,
+/// then k is doubled until an alignment within k is found or k reaches max_k.
+/// This gives the speed of a small k when distance is expected to be small while guaranteeing a result
+/// up to max_k. All other parameters are taken from base_config.
+/// Returns the first successful alignment, or the result of the last attempt (with editDistance -1)
+/// if distance is larger than max_k.
+#[allow(non_snake_case)]
+pub fn edlibAlignAdaptiveRs(
+    query: &[u8],
+    target: &[u8],
+    base_config: &EdlibAlignConfigRs,
+    initial_k: i32,
+    max_k: i32,
+) -> EdlibAlignResultRs {
+    assert!(
+        initial_k >= 0 && max_k >= 0,
+        "k bounds must be non negative"
+    );
+    let mut config = *base_config;
+    let mut k = initial_k;
+    loop {
+        config.k = k.min(max_k);
+        let align_res = edlibAlignRs(query, target, &config);
+        if align_res.status != EDLIB_STATUS_OK || align_res.editDistance >= 0 || config.k >= max_k {
+            return align_res;
+        }
+        k = k.saturating_mul(2).max(1);
+    }
+}
+
+/// Returns true if edit distance between query and target in given mode is at most k.
+/// The alignment runs with task EDLIB_TASK_DISTANCE and k set, which is the fastest edlib mode
+/// as edlib stops as soon as it knows distance is larger than k.
+/// A negative k means no bound so the result is then always true.
+#[allow(non_snake_case)]
+pub fn edlibWithinDistanceRs(query: &[u8], target: &[u8], mode: EdlibAlignModeRs, k: i32) -> bool {
+    let config = EdlibAlignConfigRs::new(k, mode, EdlibAlignTaskRs::EDLIB_TASK_DISTANCE, &[]);
+    let align_res = edlibAlignRs(query, target, &config);
+    align_res.status == EDLIB_STATUS_OK && align_res.editDistance >= 0
+}
+
+/// Aligns query against target\[window\] only, as when a seed already gives the region where query maps,
+/// then offsets start and end locations by window.start so they are positions in the whole target.
+/// The window is clamped to target.len() (and its start to its end).
+/// With an empty window, alignment is against an empty target as in edlib : in HW and SHW modes
+/// editDistance is query.len() and the end location is window.start - 1, just before the window.
+#[allow(non_snake_case)]
+pub fn edlibAlignWindowRs(
+    query: &[u8],
+    target: &[u8],
+    window: std::ops::Range<usize>,
+    config: &EdlibAlignConfigRs,
+) -> EdlibAlignResultRs {
+    let end = window.end.min(target.len());
+    let start = window.start.min(end);
+    let mut align_res = edlibAlignRs(query, &target[start..end], config);
+    let offset = start as i32;
+    let locations = align_res
+        .endLocations
+        .iter_mut()
+        .chain(align_res.startLocations.iter_mut())
+        .flatten();
+    for location in locations {
+        *location += offset;
+    }
+    align_res
+} // end of edlibAlignWindowRs
+
+/// Aligns query in mode EDLIB_MODE_HW against a target read from target_reader, without holding the whole target
+/// in memory : the target is read by chunks of chunk_size bytes, and query is aligned against windows made of
+/// the last overlap bytes of the previous window followed by the new chunk. Other parameters are taken from config.
+/// Every location found in a window is a location in the whole target, and an optimal alignment spanning at most
+/// overlap + 1 target bases lies entirely in a window. So overlap should be at least query.len() plus the largest
+/// distance searched, otherwise hits straddling a chunk boundary can be missed.
+/// The result has the smallest distance found, with the locations (in absolute target positions, sorted by end
+/// and without duplicates from overlaps) of all windows reaching it. With task EDLIB_TASK_PATH, the alignment path is
+/// the one of the first location. A read error gives a result with status EDLIB_STATUS_ERROR.
+///
+/// **Panics** if chunk_size is 0.
+#[allow(non_snake_case)]
+pub fn edlibAlignHwChunked<R: std::io::Read>(
+    query: &[u8],
+    mut target_reader: R,
+    chunk_size: usize,
+    overlap: usize,
+    config: &EdlibAlignConfigRs,
+) -> EdlibAlignResultRs {
+    use std::io::Read;
+    assert!(chunk_size > 0, "chunk_size must be positive");
+    let config_hw = EdlibAlignConfigRs {
+        mode: EdlibAlignModeRs::EDLIB_MODE_HW,
+        ..*config
+    };
+    let mut window = Vec::<u8>::with_capacity(chunk_size + overlap);
+    let mut window_start = 0usize;
+    let mut best: Option<EdlibAlignResultRs> = None;
+    let mut locations: Vec<(i32, Option<i32>)> = Vec::new();
+    loop {
+        // keep the last overlap bytes of previous window
+        let drop = window.len().saturating_sub(overlap);
+        window.drain(..drop);
+        window_start += drop;
+        let read = match target_reader
+            .by_ref()
+            .take(chunk_size as u64)
+            .read_to_end(&mut window)
+        {
+            Ok(read) => read,
+            Err(_) => {
+                return EdlibAlignResultRs {
+                    status: EDLIB_STATUS_ERROR,
+                    ..Default::default()
+                }
+            }
+        };
+        if read == 0 && best.is_some() {
+            break;
+        }
+        let align_res = edlibAlignRs(query, &window, &config_hw);
+        if align_res.status != EDLIB_STATUS_OK {
+            return align_res;
+        }
+        let offset = window_start as i32;
+        let window_locations: Vec<(i32, Option<i32>)> = align_res
+            .locations()
+            .iter()
+            .map(|&(end, start)| (end + offset, start.map(|start| start + offset)))
+            .collect();
+        let distance = align_res.editDistance;
+        let best_distance = best.as_ref().map(|b| b.editDistance);
+        // first window or better distance : locations are replaced, same distance : they are added
+        let better = match best_distance {
+            None => true,
+            Some(d) => distance >= 0 && (d < 0 || distance < d),
+        };
+        if better {
+            locations = window_locations;
+            best = Some(align_res);
+        } else if distance >= 0 && best_distance == Some(distance) {
+            locations.extend(window_locations);
+        }
+        if read < chunk_size {
+            break;
+        }
+    }
+    let mut align_res = best.unwrap_or_default();
+    if align_res.editDistance >= 0 {
+        locations.sort_unstable();
+        locations.dedup();
+        align_res.numLocations = locations.len();
+        align_res.endLocations = Some(locations.iter().map(|&(end, _)| end).collect());
+        align_res.startLocations = locations.iter().map(|&(_, start)| start).collect();
+    }
+    align_res
+} // end of edlibAlignHwChunked
+
+/// Runs a PATH alignment of query against target and tells if its identity is at least min_identity,
+/// as read mappers filter hits. Other parameters of config are used as given.
+/// Identity is the number of matches (opcode 0, which includes pairs made equal by additionalequalities)
+/// divided by the alignment length, so mismatches and gaps are counted against all alignment columns.
+/// This is not 1 - edit distance / query length.
+/// Returns None if no alignment path was produced (for example if edit distance is larger than k).
+pub fn passes_identity_threshold(
+    query: &[u8],
+    target: &[u8],
+    config: &EdlibAlignConfigRs,
+    min_identity: f64,
+) -> Option<bool> {
+    let mut config_path = *config;
+    config_path.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+    let align_res = edlibAlignRs(query, target, &config_path);
+    if align_res.status != EDLIB_STATUS_OK {
+        return None;
+    }
+    let alignment = align_res.alignment()?;
+    let matches = alignment
+        .iter()
+        .filter(|&&op| op == EDLIB_EDOP_MATCH as u8)
+        .count();
+    let identity = matches as f64 / alignment.len() as f64;
+    Some(identity >= min_identity)
+} // end of passes_identity_threshold
+
+/// Returns the sorted distinct bytes of query and target, which is the alphabet edlib builds internally
+/// (edlib orders it by first occurrence, query first, but the set is the same).
+/// Its length is the alphabetLength field of an alignment result of query and target.
+/// additionalequalities do not change the alphabet : a pair involving a byte absent from both sequences
+/// is ignored by edlib and equal bytes are still counted as distinct symbols.
+pub fn compute_alphabet(query: &[u8], target: &[u8]) -> Vec<u8> {
+    let mut present = [false; 256];
+    for &c in query.iter().chain(target.iter()) {
+        present[c as usize] = true;
+    }
+    (0..=255u8).filter(|&c| present[c as usize]).collect()
+} // end of compute_alphabet
+
+/// Error returned by check_ascii, giving the first non ASCII byte found and its position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NonAsciiError {
+    /// non ASCII byte in query
+    Query { position: usize, byte: u8 },
+    /// non ASCII byte in target
+    Target { position: usize, byte: u8 },
+}
+
+impl fmt::Display for NonAsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NonAsciiError::Query { position, byte } => {
+                write!(
+                    f,
+                    "non ASCII byte {:#04x} in query at position {}",
+                    byte, position
+                )
+            }
+            NonAsciiError::Target { position, byte } => {
+                write!(
+                    f,
+                    "non ASCII byte {:#04x} in target at position {}",
+                    byte, position
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for NonAsciiError {}
+
+/// Checks that query and target contain only ASCII bytes, query being checked first.
+/// edlib works on bytes, so UTF-8 text with non ASCII chars is aligned byte by byte : each such char
+/// counts as several symbols, grows alphabetLength and can slow the alignment.
+pub fn check_ascii(query: &[u8], target: &[u8]) -> Result<(), NonAsciiError> {
+    if let Some(position) = query.iter().position(|c| !c.is_ascii()) {
+        return Err(NonAsciiError::Query {
+            position,
+            byte: query[position],
+        });
+    }
+    if let Some(position) = target.iter().position(|c| !c.is_ascii()) {
+        return Err(NonAsciiError::Target {
+            position,
+            byte: target[position],
+        });
+    }
+    Ok(())
+}
+
+/// Same as edlibAlignRs but runs check_ascii first and returns its error instead of aligning
+/// if query or target contains a non ASCII byte.
+#[allow(non_snake_case)]
+pub fn edlibAlignAsciiRs(
+    query: &[u8],
+    target: &[u8],
+    config: &EdlibAlignConfigRs,
+) -> Result<EdlibAlignResultRs, NonAsciiError> {
+    check_ascii(query, target)?;
+    Ok(edlibAlignRs(query, target, config))
+}
+
+/// Aligns query against target after replacing each byte b of both sequences by remap\[b\], so that bytes
+/// mapped to the same value are identical for edlib (for example amino acids collapsed into groups).
+/// Fewer distinct symbols make a smaller alphabet, and edlib is faster with a small alphabet.
+/// Remapping preserves positions, so locations and alignment path are valid for the original sequences.
+/// alphabetLength is the size of the remapped alphabet, and additional equalities of config apply to remapped bytes.
+pub fn align_with_remap(
+    query: &[u8],
+    target: &[u8],
+    remap: &[u8; 256],
+    config: &EdlibAlignConfigRs,
+) -> EdlibAlignResultRs {
+    let query: Vec<u8> = query.iter().map(|&c| remap[c as usize]).collect();
+    let target: Vec<u8> = target.iter().map(|&c| remap[c as usize]).collect();
+    edlibAlignRs(&query, &target, config)
+}
+
+/// Error returned by edlibAlignSafe when an alignment is not attempted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocError {
+    /// query or target longer than i32::MAX, the largest length edlib accepts
+    SequenceTooLong { len: usize },
+    /// the estimated memory needed by edlib (in bytes) could not be reserved
+    OutOfMemory { bytes: usize },
+}
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AllocError::SequenceTooLong { len } => {
+                write!(
+                    f,
+                    "sequence length {} exceeds edlib limit {}",
+                    len,
+                    i32::MAX
+                )
+            }
+            AllocError::OutOfMemory { bytes } => {
+                write!(f, "cannot reserve {} bytes for alignment", bytes)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AllocError {}
+
+// Rough upper bound of edlib peak memory : copies and reversed copies of the sequences and the path,
+// Peq table of (alphabet + 1) x blocks words, block arrays, and at most 1MB of traceback data
+// (edlib switches to Hirschberg's algorithm above).
+#[allow(non_snake_case)]
+fn estimatedEdlibMemory(query_len: usize, target_len: usize) -> Option<usize> {
+    let blocks = query_len.checked_add(63)? / 64;
+    let sequences = query_len.checked_add(target_len)?.checked_mul(4)?;
+    let peq = blocks.checked_mul(257 * 8)?;
+    let block_arrays = blocks.checked_mul(64)?;
+    sequences
+        .checked_add(peq)?
+        .checked_add(block_arrays)?
+        .checked_add(1024 * 1024)
+}
+
+/// Same as edlibAlignRs but checks before calling edlib that sequence lengths fit edlib int lengths
+/// and that the estimated memory edlib needs can be reserved, returning an error instead of aligning otherwise.
+///
+/// edlib is C++ and allocates with new, which throws std::bad_alloc when memory is exhausted.
+/// A C++ exception cannot be caught in Rust and must not unwind through the extern "C" call : this is
+/// undefined behaviour, in practice the unwinder finds no handler and the process aborts (std::terminate).
+/// Compiling edlib with -fno-exceptions would not change this as the exception is thrown by the C++ runtime.
+/// The checks make this failure unlikely but cannot exclude it, as memory can be taken by others
+/// between the check and the alignment, and as an overcommitting system may accept the reservation anyway.
+#[allow(non_snake_case)]
+pub fn edlibAlignSafe(
+    query: &[u8],
+    target: &[u8],
+    config: &EdlibAlignConfigRs,
+) -> Result<EdlibAlignResultRs, AllocError> {
+    for len in [query.len(), target.len()].iter().copied() {
+        if len > i32::MAX as usize {
+            return Err(AllocError::SequenceTooLong { len });
+        }
+    }
+    let bytes = estimatedEdlibMemory(query.len(), target.len()).unwrap_or(usize::MAX);
+    // reserve and release the memory to check it is available
+    let mut probe = Vec::<u8>::new();
+    if probe.try_reserve_exact(bytes).is_err() {
+        return Err(AllocError::OutOfMemory { bytes });
+    }
+    drop(probe);
+    Ok(edlibAlignRs(query, target, config))
+}
+
+/// Error returned by edlibAlignWithTimeout when the alignment did not finish in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeoutError {
+    /// the timeout that expired
+    pub timeout: std::time::Duration,
+}
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "alignment not finished after {:?}", self.timeout)
+    }
+}
+
+impl std::error::Error for TimeoutError {}
+
+/// Same as edlibAlignRs but waits for the result at most timeout.
+/// The call to edlib is synchronous and cannot be interrupted, so the alignment runs on a worker thread
+/// (with copies of query, target and equalities) and its result is awaited on a channel.
+/// On timeout the computation is **not cancelled**, only abandoned : the detached worker thread keeps running
+/// until edlib returns, using its CPU and memory, and its result is dropped.
+#[allow(non_snake_case)]
+pub fn edlibAlignWithTimeout(
+    query: &[u8],
+    target: &[u8],
+    config: &EdlibAlignConfigRs,
+    timeout: std::time::Duration,
+) -> Result<EdlibAlignResultRs, TimeoutError> {
+    let query = query.to_vec();
+    let target = target.to_vec();
+    let config = EdlibAlignConfigOwned::from(config);
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let align_res = edlibAlignRs(&query, &target, &config.as_borrowed());
+        // the receiver is gone if we timed out, the result is then dropped
+        let _ = sender.send(align_res);
+    });
+    receiver
+        .recv_timeout(timeout)
+        .map_err(|_| TimeoutError { timeout })
+}
+
+/// Aligns query against a prefix of target (mode EDLIB_MODE_SHW) and fills startLocations.
+/// The task of config is raised to EDLIB_TASK_LOC if it was EDLIB_TASK_DISTANCE, a PATH task is kept,
+/// other parameters are used as given.
+/// In SHW mode only the gap at the end of target is free, so every optimal alignment starts at target position 0
+/// and all start locations are 0: no alignment of the reversed sequences is needed to find them.
+/// (Aligning reversed query against reversed target\[..=end\] in SHW mode is how edlib recovers
+/// start locations in HW mode, where the start is free too.) The task LOC only makes edlib fill them.
+#[allow(non_snake_case)]
+pub fn edlibAlignShwWithStart(
+    query: &[u8],
+    target: &[u8],
+    config: &EdlibAlignConfigRs,
+) -> EdlibAlignResultRs {
+    let mut config_loc = *config;
+    config_loc.mode = EdlibAlignModeRs::EDLIB_MODE_SHW;
+    if matches!(config_loc.task, EdlibAlignTaskRs::EDLIB_TASK_DISTANCE) {
+        config_loc.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+    }
+    edlibAlignRs(query, target, &config_loc)
+} // end of edlibAlignShwWithStart
+
+/// Aligns query against a suffix of target : the gap before the aligned part of target is free, as in HW mode,
+/// but the alignment must reach the end of target, the mirror of the prefix mode EDLIB_MODE_SHW.
+/// The mode of config is ignored, other parameters are used as given.
+///
+/// edlib has no such mode, so reversed query is aligned against reversed target in mode EDLIB_MODE_SHW and the
+/// result is translated back to target coordinates, with t = target.len() :
+///   * start and end swap roles : a reversed end location e gives the start location t - 1 - e,
+///     and the reversed start, always 0 in SHW mode, gives the end location t - 1 for every location.
+///   * locations keep edlib order of reversed ends, so start locations come in decreasing order,
+///     the first one being the one of the alignment path.
+///   * the alignment path is reversed. Opcodes are unchanged, as both sequences are reversed.
+///
+/// As they come from reversed end locations, start locations are filled for all tasks, even EDLIB_TASK_DISTANCE.
+#[allow(non_snake_case)]
+pub fn edlibAlignSuffixRs(
+    query: &[u8],
+    target: &[u8],
+    config: &EdlibAlignConfigRs,
+) -> EdlibAlignResultRs {
+    let rev_query: Vec<u8> = query.iter().rev().copied().collect();
+    let rev_target: Vec<u8> = target.iter().rev().copied().collect();
+    let config_rev = EdlibAlignConfigRs {
+        mode: EdlibAlignModeRs::EDLIB_MODE_SHW,
+        ..*config
+    };
+    let mut align_res = edlibAlignRs(&rev_query, &rev_target, &config_rev);
+    let t = target.len() as i32;
+    if let Some(rev_ends) = align_res.endLocations.take() {
+        align_res.startLocations = Some(rev_ends.iter().map(|&e| t - 1 - e).collect());
+        align_res.endLocations = Some(vec![t - 1; rev_ends.len()]);
+    } else {
+        align_res.startLocations = None;
+    }
+    if let Some(alignment) = align_res.alignment.as_mut() {
+        alignment.reverse();
+    }
+    align_res
+} // end of edlibAlignSuffixRs
+
+/// Aligns query against target in mode EDLIB_MODE_HW with task EDLIB_TASK_PATH, other parameters of config being
+/// used as given, and keeps the hit only if its alignment path has at least min_matches match opcodes.
+/// In HW mode a short query can be placed anywhere with free flanks, so a hit made mostly of edits is spurious.
+/// A rejected hit is reported as a distance larger than k : editDistance is -1, without locations nor path.
+#[allow(non_snake_case)]
+pub fn edlibAlignHwAnchored(
+    query: &[u8],
+    target: &[u8],
+    min_matches: usize,
+    config: &EdlibAlignConfigRs,
+) -> EdlibAlignResultRs {
+    let config_path = EdlibAlignConfigRs {
+        mode: EdlibAlignModeRs::EDLIB_MODE_HW,
+        task: EdlibAlignTaskRs::EDLIB_TASK_PATH,
+        ..*config
+    };
+    let mut align_res = edlibAlignRs(query, target, &config_path);
+    if align_res.status != EDLIB_STATUS_OK || align_res.editDistance < 0 {
+        return align_res;
+    }
+    let matches = align_res
+        .paf_match_block()
+        .map_or(0, |(matches, _)| matches);
+    if matches < min_matches {
+        align_res.editDistance = -1;
+        align_res.endLocations = None;
+        align_res.startLocations = None;
+        align_res.numLocations = 0;
+        align_res.alignment = None;
+    }
+    align_res
+} // end of edlibAlignHwAnchored
+
+/// Method syntax for alignments, implemented for \[u8\] and str :
+///
+/// ```
+/// use edlib_rs::*;
+/// assert_eq!("ACCTCTG".edit_distance("ACTCTGAAA"), Some(4));
+/// let align_res = b"ACCTCTG"[..].align(b"TTACTCTGAAA", &EdlibAlignConfigRs::infix());
+/// assert_eq!(align_res.editDistance, 1);
+/// ```
+pub trait AlignExt {
+    /// the bytes of the sequence
+    fn seq_bytes(&self) -> &[u8];
+
+    /// NW edit distance of self (as query) to other, computed with task EDLIB_TASK_DISTANCE.
+    fn edit_distance<T: AsRef<[u8]> + ?Sized>(&self, other: &T) -> Option<u32> {
+        edlibAlignRs(
+            self.seq_bytes(),
+            other.as_ref(),
+            &EdlibAlignConfigRs::default(),
+        )
+        .distance()
+    }
+
+    /// aligns self as query against other as target, see edlibAlignRs.
+    fn align<T: AsRef<[u8]> + ?Sized>(
+        &self,
+        other: &T,
+        config: &EdlibAlignConfigRs,
+    ) -> EdlibAlignResultRs {
+        edlibAlignRs(self.seq_bytes(), other.as_ref(), config)
+    }
+}
+
+impl AlignExt for [u8] {
+    fn seq_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+impl AlignExt for str {
+    fn seq_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+// edlib has no dedicated function to release the cigar string : edlibAlignmentToCigar builds it in a
+// std::vector then copies it in a buffer obtained from malloc (not new[]), and its documentation asks
+// to release it with free(). We link to the C runtime free so allocation and deallocation match.
+extern "C" {
+    fn free(ptr: *mut std::os::raw::c_void);
+}
+
+/// Builds cigar string from given alignment sequence.
+///  param : alignment  Alignment sequence.
+///  (is obtained from EdlibAlignResultRs.alignment which is a Some if EdlibAlignConfigRs.task is set to EdlibAlignTaskRs::EDLIB_TASK_PATH
+///  see *test_path_hw*)
+///     *  0 stands for match.
+///     *  1 stands for insertion to target.
+///     *  2 stands for insertion to query.
+///     *  3 stands for mismatch.
+///  param cigarFormat  Cigar will be returned in specified format.
+///
+///   return Cigar string where :
+///    * I stands for insertion.
 ///    * D stands for deletion.
 ///    * X stands for mismatch. (used only in extended format)
 ///    * = stands for match. (used only in extended format)
 ///    * M stands for (mis)match. (used only in standard format)
-
+///
+/// **Panics** if alignment contains an opcode greater than 3, see edlibTryAlignmentToCigarRs for a non panicking version.
+#[allow(non_snake_case)]
 pub fn edlibAlignmentToCigarRs(alignment: &[u8], cigarFormat: &EdlibCigarFormatRs) -> String {
+    match edlibTryAlignmentToCigarRs(alignment, cigarFormat) {
+        Ok(cigar) => cigar,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+/// Error returned by edlibTryAlignmentToCigarRs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CigarError {
+    /// Opcode greater than 3, with its position in alignment.
+    InvalidOpcode(usize, u8),
+    /// edlib returned a null cigar string.
+    NullCigar,
+}
+
+impl fmt::Display for CigarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CigarError::InvalidOpcode(pos, op) => {
+                write!(f, "invalid alignment opcode {} at position {}", op, pos)
+            }
+            CigarError::NullCigar => write!(f, "null cigar string returned from C"),
+        }
+    }
+}
+
+impl std::error::Error for CigarError {}
+
+/// Same as edlibAlignmentToCigarRs but returns an error instead of panicking.
+/// Opcodes are checked before calling edlib. An empty alignment gives an empty cigar.
+#[allow(non_snake_case)]
+pub fn edlibTryAlignmentToCigarRs(
+    alignment: &[u8],
+    cigarFormat: &EdlibCigarFormatRs,
+) -> Result<String, CigarError> {
+    if let Some(pos) = alignment.iter().position(|&op| op > 3) {
+        return Err(CigarError::InvalidOpcode(pos, alignment[pos]));
+    }
     // convert cigarFormat to C arg
     let cigarstring: String;
     unsafe {
         let c_res: *const c_char = edlibAlignmentToCigar(
             alignment.as_ptr(),
             alignment.len() as i32,
-            *cigarFormat as u32,
-        );
-        assert!(
-            c_res != std::ptr::null_mut(),
-            "null cigar string returnd from C"
+            *cigarFormat as EdlibCigarFormat,
         );
+        if c_res.is_null() {
+            return Err(CigarError::NullCigar);
+        }
         cigarstring = ::std::ffi::CStr::from_ptr(c_res)
             .to_string_lossy()
             .into_owned();
-        free(c_res);
+        // buffer was allocated by malloc in edlibAlignmentToCigar
+        free(c_res as *mut std::os::raw::c_void);
+    }
+    Ok(cigarstring)
+}
+
+/// Aligns query and target and builds the cigar string of the alignment in one call.
+/// The task of config is forced to EDLIB_TASK_PATH, other parameters are used as given.
+/// Returns None if no alignment path was produced (for example if edit distance is larger than k).
+#[allow(non_snake_case)]
+pub fn edlibAlignToCigarRs(
+    query: &[u8],
+    target: &[u8],
+    config: &EdlibAlignConfigRs,
+    format: EdlibCigarFormatRs,
+) -> Option<String> {
+    let mut config_path = *config;
+    config_path.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+    let align_res = edlibAlignRs(query, target, &config_path);
+    if align_res.status != EDLIB_STATUS_OK {
+        return None;
     }
-    cigarstring
+    align_res
+        .alignment()
+        .map(|alignment| edlibAlignmentToCigarRs(alignment, &format))
+}
+
+/// Builds a cigar string for each location of result, as edlib only computes the alignment path of the first one.
+/// For each (start, end) location, query is aligned in EDLIB_MODE_NW against target\[start..=end\],
+/// with the other parameters of config (the one used to compute result, so that additional equalities
+/// score the same), and the cigar of this alignment is returned.
+/// Locations with an empty aligned target span (end < start, as for an empty target) or outside target are skipped.
+/// Returns an empty Vec if start locations were not computed (task EDLIB_TASK_DISTANCE).
+pub fn cigars_for_all_locations(
+    query: &[u8],
+    target: &[u8],
+    result: &EdlibAlignResultRs,
+    config: &EdlibAlignConfigRs,
+    format: EdlibCigarFormatRs,
+) -> Vec<String> {
+    let config_path = EdlibAlignConfigRs {
+        mode: EdlibAlignModeRs::EDLIB_MODE_NW,
+        task: EdlibAlignTaskRs::EDLIB_TASK_PATH,
+        ..*config
+    };
+    result
+        .locations()
+        .iter()
+        .filter_map(|&(end, start)| {
+            let start = start?;
+            if start < 0 || end < start {
+                return None;
+            }
+            let span = target.get(start as usize..=end as usize)?;
+            let align_res = edlibAlignRs(query, span, &config_path);
+            align_res
+                .alignment()
+                .map(|alignment| edlibAlignmentToCigarRs(alignment, &format))
+        })
+        .collect()
+}
+
+/// Policy choosing the location whose alignment path is computed by align_best_location.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LocationPolicy {
+    /// smallest start location (then smallest end).
+    Leftmost,
+    /// largest end location (then largest start).
+    Rightmost,
+    /// smallest aligned target span end - start (then smallest start).
+    Shortest,
 }
 
+/// Aligns query against target and computes the alignment path of the location chosen by policy,
+/// when there are several optimal locations (edlib computes the path of the first one only).
+/// Locations are found with task EDLIB_TASK_LOC, then query is realigned with mode EDLIB_MODE_NW and
+/// task EDLIB_TASK_PATH against target\[start..=end\] of the chosen location, with the other parameters of config.
+/// In the result the chosen location is moved first in startLocations and endLocations, the others follow in order,
+/// so that functions using startLocations\[0\] with the path (formats, coordinate_pairs...) use the chosen one.
+/// If no location was found (distance larger than k) the result of the LOC alignment is returned.
+pub fn align_best_location(
+    query: &[u8],
+    target: &[u8],
+    config: &EdlibAlignConfigRs,
+    policy: LocationPolicy,
+) -> EdlibAlignResultRs {
+    let mut config_loc = *config;
+    config_loc.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+    let mut align_res = edlibAlignRs(query, target, &config_loc);
+    let locations: Vec<(i32, i32)> = align_res
+        .locations()
+        .iter()
+        .filter_map(|&(end, start)| Some((start?, end)))
+        .collect();
+    let best = match policy {
+        LocationPolicy::Leftmost => locations
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, &(start, end))| (start, end)),
+        LocationPolicy::Rightmost => locations
+            .iter()
+            .enumerate()
+            .max_by_key(|&(_, &(start, end))| (end, start)),
+        LocationPolicy::Shortest => locations
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, &(start, end))| (end - start, start)),
+    };
+    let (i, (start, end)) = match best {
+        Some((i, &location)) => (i, location),
+        None => return align_res,
+    };
+    for locations in [&mut align_res.startLocations, &mut align_res.endLocations].iter_mut() {
+        if let Some(locations) = locations.as_mut() {
+            let location = locations.remove(i);
+            locations.insert(0, location);
+        }
+    }
+    let config_path = EdlibAlignConfigRs {
+        mode: EdlibAlignModeRs::EDLIB_MODE_NW,
+        task: EdlibAlignTaskRs::EDLIB_TASK_PATH,
+        ..*config
+    };
+    // end is -1 when query is best aligned before target start
+    let span = &target[start as usize..(end + 1).max(start) as usize];
+    let path_res = edlibAlignRs(query, span, &config_path);
+    align_res.alignment = path_res.alignment;
+    align_res
+} // end of align_best_location
+
+/// Groups the locations of result whose end locations are within max_gap of the previous one (locations are
+/// sorted by end), as in HW mode edlib can report several ends for the same hit, shifted by a few free flank bases.
+/// Returns one (start, end) per cluster : the location with the shortest aligned target span, then the
+/// smallest start, as LocationPolicy::Shortest. All locations have the same optimal distance.
+/// If start locations were not computed (task EDLIB_TASK_DISTANCE) start is -1 and the first end of
+/// each cluster is returned.
+/// Returns an empty vector if result has no location.
+pub fn cluster_locations(result: &EdlibAlignResultRs, max_gap: i32) -> Vec<(i32, i32)> {
+    let mut clusters: Vec<(i32, i32)> = Vec::new();
+    let mut last_end: Option<i32> = None;
+    for (end, start) in result.locations() {
+        let location = (start.unwrap_or(-1), end);
+        let same_cluster = matches!(last_end, Some(last) if end - last <= max_gap);
+        last_end = Some(end);
+        match clusters.last_mut() {
+            Some(best) if same_cluster => {
+                if start.is_some()
+                    && (location.1 - location.0, location.0) < (best.1 - best.0, best.0)
+                {
+                    *best = location;
+                }
+            }
+            _ => clusters.push(location),
+        }
+    }
+    clusters
+} // end of cluster_locations
+
 //===================================================================
 
 #[cfg(test)]
@@ -396,7 +1975,7 @@ mod tests {
             &EdlibAlignConfigRs::default(),
         );
         assert_eq!(align_res.status, EDLIB_STATUS_OK);
-        assert_eq!(align_res.getDistance(), 4);
+        assert_eq!(align_res.edit_distance(), 4);
     } // end test_distance_nw
 
     #[test]
@@ -446,6 +2025,110 @@ mod tests {
         assert_eq!(align_res.editDistance, 1);
     } // end of test_distance_with_pair
 
+    #[test]
+    fn test_equality_pair_new() {
+        let pair = EdlibEqualityPairRs::new(b'A', b'N');
+        assert_eq!(pair.first(), b'A');
+        assert_eq!(pair.second(), b'N');
+        let equalitypairs = vec![pair, EdlibEqualityPairRs::from_chars('G', 'X')];
+        assert_eq!(equalitypairs[1].first(), b'G');
+        assert_eq!(equalitypairs[1].second(), b'X');
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.additionalequalities = &equalitypairs;
+        let align_res = edlibAlignRs(b"ACCTCTG", b"TTTTTTTTTTTTTTTTTTTTTNCTCTXAAA", &config);
+        assert_eq!(align_res.status, EDLIB_STATUS_OK);
+        assert_eq!(align_res.editDistance, 1);
+    } // end of test_equality_pair_new
+
+    #[test]
+    fn test_equality_pair_high_byte() {
+        let pair = EdlibEqualityPairRs::new(200, 201);
+        assert_eq!(pair.first(), 200);
+        assert_eq!(pair.second(), 201);
+        let query: &[u8] = &[b'A', 200, b'C', 255];
+        let target: &[u8] = &[b'A', 201, b'C', 128];
+        let config = EdlibAlignConfigRs::default();
+        assert_eq!(edlibAlignRs(query, target, &config).editDistance, 2);
+        let pairs = [pair, EdlibEqualityPairRs::new(255, 128)];
+        let config = EdlibAlignConfigRs {
+            additionalequalities: &pairs,
+            ..Default::default()
+        };
+        assert_eq!(edlibAlignRs(query, target, &config).editDistance, 0);
+    } // end of test_equality_pair_high_byte
+
+    #[test]
+    fn test_with_char_equalities() {
+        let query = "ACCTCTG";
+        let target = "TTTTTTTTTTTTTTTTTTTTTNCTCTXAAA";
+        let config = EdlibAlignConfigRs::infix().with_char_equalities(&[('A', 'N'), ('G', 'X')]);
+        assert_eq!(config.additionalequalities.len(), 2);
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config.as_borrowed());
+        assert_eq!(align_res.status, EDLIB_STATUS_OK);
+        assert_eq!(align_res.editDistance, 1);
+    } // end of test_with_char_equalities
+
+    #[test]
+    fn test_config_owned() {
+        struct Aligner {
+            config: EdlibAlignConfigOwned,
+        }
+        let aligner = Aligner {
+            config: EdlibAlignConfigOwned {
+                mode: EdlibAlignModeRs::EDLIB_MODE_HW,
+                additionalequalities: vec![EdlibEqualityPairRs {
+                    first: 'A' as c_char,
+                    second: 'N' as c_char,
+                }],
+                ..Default::default()
+            },
+        };
+        let align_res = edlibAlignRs(
+            b"ACCTCTG",
+            b"TTTTTTTTTTNCCTCTGTTT",
+            &aligner.config.as_borrowed(),
+        );
+        assert_eq!(align_res.status, EDLIB_STATUS_OK);
+        assert_eq!(align_res.editDistance, 0);
+        //
+        let config = EdlibAlignConfigOwned::from(&EdlibAlignConfigRs::infix());
+        assert!(matches!(config.mode, EdlibAlignModeRs::EDLIB_MODE_HW));
+        assert!(config.additionalequalities.is_empty());
+    } // end of test_config_owned
+
+    #[test]
+    fn test_softmask_insensitive() {
+        let config = EdlibAlignConfigOwned::default().with_softmask_insensitive();
+        assert_eq!(config.additionalequalities.len(), 5);
+        let align_res = edlibAlignRs(b"acgt", b"ACGT", &config.as_borrowed());
+        assert_eq!(align_res.status, EDLIB_STATUS_OK);
+        assert_eq!(align_res.editDistance, 0);
+        let align_res = edlibAlignRs(b"ACGTNacgtn", b"acgtnACGTN", &config.as_borrowed());
+        assert_eq!(align_res.editDistance, 0);
+        // other letters are still case sensitive
+        let align_res = edlibAlignRs(b"ACGu", b"ACGU", &config.as_borrowed());
+        assert_eq!(align_res.editDistance, 1);
+    } // end of test_softmask_insensitive
+
+    #[test]
+    fn test_bounded() {
+        assert!(!EdlibAlignConfigRs::default().is_bounded());
+        assert!(EdlibAlignConfigRs::bounded(0).is_bounded());
+        let align_res = edlibAlignRs(b"ACCTCTG", b"ACTCTGAAA", &EdlibAlignConfigRs::bounded(4));
+        assert_eq!(align_res.editDistance, 4);
+    } // end of test_bounded
+
+    #[test]
+    fn test_with_normalized_equalities() {
+        let config = EdlibAlignConfigRs::infix()
+            .with_char_equalities(&[('A', 'N'), ('A', 'N')])
+            .with_normalized_equalities();
+        assert_eq!(config.additionalequalities.len(), 2);
+        let align_res = edlibAlignRs(b"ACCTCTG", b"TTTTTTTTTTNCCTCTGTTT", &config.as_borrowed());
+        assert_eq!(align_res.editDistance, 0);
+    } // end of test_with_normalized_equalities
+
     #[test]
     fn test_path_hw() {
         let query = "missing";
@@ -460,7 +2143,7 @@ mod tests {
         assert!(align_res.startLocations.is_some());
         assert!(align_res.endLocations.is_some());
         //
-        assert!(align_res.getAlignment().is_some());
+        assert!(align_res.alignment().is_some());
 
         let cigar = edlibAlignmentToCigarRs(
             align_res.alignment.as_ref().unwrap(),
@@ -471,7 +2154,7 @@ mod tests {
         assert_eq!(cigar, "5M2I");
 
         let cigarx = edlibAlignmentToCigarRs(
-            align_res.getAlignment().unwrap(),
+            align_res.alignment().unwrap(),
             &EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED,
         );
         // answer is "5=2I"
@@ -480,14 +2163,1135 @@ mod tests {
     } // end of test_path_hw
 
     #[test]
-    fn test_distance_nw_with_max_k() {
+    fn test_try_cigar() {
+        let format = EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD;
+        assert_eq!(edlibTryAlignmentToCigarRs(&[], &format), Ok(String::new()));
+        assert_eq!(edlibAlignmentToCigarRs(&[], &format), "");
+        assert_eq!(
+            edlibTryAlignmentToCigarRs(&[0, 0, 3, 1], &format),
+            Ok(String::from("3M1I"))
+        );
+        assert_eq!(
+            edlibTryAlignmentToCigarRs(&[0, 0, 4, 1], &format),
+            Err(CigarError::InvalidOpcode(2, 4))
+        );
+    } // end of test_try_cigar
+
+    #[test]
+    fn test_cigar_repeated() {
+        // many conversions, each buffer from edlib is released with free. Run under a sanitizer
+        // (RUSTFLAGS=-Zsanitizer=address) to check allocation and deallocation match.
+        let alignment = [0, 0, 3, 1, 2, 2, 0];
+        for _ in 0..1000 {
+            let cigar =
+                edlibAlignmentToCigarRs(&alignment, &EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED);
+            assert_eq!(cigar, "2=1X1I2D1=");
+        }
+    } // end of test_cigar_repeated
+
+    #[test]
+    fn test_coordinate_pairs() {
+        let query = "missing";
+        let target = "mississipi";
+        //
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        // alignment is 5M2I
+        let pairs = align_res.coordinate_pairs(0).unwrap();
+        assert_eq!(pairs.len(), 7);
+        for (i, pair) in pairs.iter().take(5).enumerate() {
+            assert_eq!(*pair, (Some(i), Some(i)));
+        }
+        assert_eq!(pairs[5], (Some(5), None));
+        assert_eq!(pairs[6], (Some(6), None));
+        // query gap in global mode, with query_start offset
+        let mut config_nw = EdlibAlignConfigRs::default();
+        config_nw.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(b"ACGT", b"ACGGT", &config_nw);
+        assert_eq!(align_res.editDistance, 1);
+        let pairs = align_res.coordinate_pairs(10).unwrap();
+        assert_eq!(pairs.len(), 5);
+        assert_eq!(pairs.iter().filter(|p| p.0.is_none()).count(), 1);
+        assert_eq!(pairs.first(), Some(&(Some(10), Some(0))));
+        assert_eq!(pairs.last(), Some(&(Some(13), Some(4))));
+        assert!(align_res.coordinate_pairs(-1).is_none());
+        // no path
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert!(align_res.coordinate_pairs(0).is_none());
+    } // end of test_coordinate_pairs
+
+    #[test]
+    fn test_gap_stats() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        // one deletion of 2 and one insertion of 1
+        let align_res = edlibAlignRs(b"AAAACCCCGGGGTTTT", b"AAAACCCCAAGGGGTTTTT", &config);
+        assert_eq!(align_res.editDistance, 3);
+        assert_eq!(align_res.gap_stats(), Some((3, 2)));
+        //
+        let align_res = EdlibAlignResultRs {
+            alignment: Some(vec![0, 1, 1, 0, 3, 2, 2, 2, 1, 0]),
+            ..Default::default()
+        };
+        assert_eq!(align_res.gap_stats(), Some((6, 3)));
+        // no path
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(b"ACGT", b"ACT", &config);
+        assert!(align_res.gap_stats().is_none());
+    } // end of test_gap_stats
+
+    #[test]
+    fn test_aligned_target_slice() {
         let query = "ACCTCTG";
-        let target = "ACTCTGAAA";
+        let target = "TTTTTTTTTTTTTTTTTTTTTACTCTGAAA";
+        //
         let mut config = EdlibAlignConfigRs::default();
-        config.k = 3;
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let slice = align_res.aligned_target_slice(target.as_bytes());
+        assert_eq!(slice, Some("ACTCTG".as_bytes()));
+        // too short target
+        assert!(align_res.aligned_target_slice(b"ACTCTG").is_none());
+        // no start locations
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_DISTANCE;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert!(align_res.aligned_target_slice(target.as_bytes()).is_none());
+    } // end of test_aligned_target_slice
+
+    #[test]
+    fn test_ranges() {
+        let query = "ACCTCTG";
+        let target = "TTTTTTTTTTTTTTTTTTTTTACTCTGAAA";
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let range = align_res.target_range().unwrap();
+        assert_eq!(&target.as_bytes()[range], "ACTCTG".as_bytes());
+        assert_eq!(
+            Some(&target.as_bytes()[align_res.target_range().unwrap()]),
+            align_res.aligned_target_slice(target.as_bytes())
+        );
+        assert_eq!(align_res.query_range(), Some(0..query.len()));
+        // no path, no start locations
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_DISTANCE;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert!(align_res.target_range().is_none());
+        assert!(align_res.query_range().is_none());
+    } // end of test_ranges
+
+    #[test]
+    fn test_normalized_distance() {
+        let config = EdlibAlignConfigRs::default();
+        let align_res = edlibAlignRs(b"ACGTACGT", b"ACGTACGT", &config);
+        assert_eq!(align_res.normalized_distance(8, 8), Some(0.));
+        let align_res = edlibAlignRs(b"AAAAAAAA", b"CCCCCCCC", &config);
+        assert_eq!(align_res.normalized_distance(8, 8), Some(1.));
+        // distance 4, longer sequence has 9 residues
+        let align_res = edlibAlignRs(b"ACCTCTG", b"ACTCTGAAA", &config);
+        assert_eq!(align_res.normalized_distance(7, 9), Some(4. / 9.));
+        assert_eq!(align_res.normalized_by_query(7), Some(4. / 7.));
+        // exceeded k
+        let config = EdlibAlignConfigRs::new(
+            3,
+            EdlibAlignModeRs::EDLIB_MODE_NW,
+            EdlibAlignTaskRs::EDLIB_TASK_DISTANCE,
+            &[],
+        );
+        let align_res = edlibAlignRs(b"ACCTCTG", b"ACTCTGAAA", &config);
+        assert!(align_res.normalized_distance(7, 9).is_none());
+    } // end of test_normalized_distance
+
+    #[test]
+    fn test_distance_nw_with_max_k() {
+        let query = "ACCTCTG";
+        let target = "ACTCTGAAA";
+        let mut config = EdlibAlignConfigRs::default();
+        config.k = 3;
         let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
         assert_eq!(align_res.status, EDLIB_STATUS_OK);
         // real distance is 4 as we asked for max dist = 3 we should get -1
-        assert_eq!(align_res.getDistance(), -1);
+        assert_eq!(align_res.edit_distance(), -1);
+        assert_eq!(align_res.distance(), None);
+        config.k = 4;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.distance(), Some(4));
     } // end test_distance_nw
+
+    #[test]
+    fn test_within_distance() {
+        let query = "ACCTCTG";
+        let target = "ACTCTGAAA";
+        let mode = EdlibAlignModeRs::EDLIB_MODE_NW;
+        // real distance is 4
+        assert!(edlibWithinDistanceRs(
+            query.as_bytes(),
+            target.as_bytes(),
+            mode,
+            5
+        ));
+        assert!(edlibWithinDistanceRs(
+            query.as_bytes(),
+            target.as_bytes(),
+            mode,
+            4
+        ));
+        assert!(!edlibWithinDistanceRs(
+            query.as_bytes(),
+            target.as_bytes(),
+            mode,
+            3
+        ));
+        assert!(edlibWithinDistanceRs(
+            query.as_bytes(),
+            target.as_bytes(),
+            mode,
+            -1
+        ));
+        // distance is 1 in infix mode
+        let mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        assert!(edlibWithinDistanceRs(
+            query.as_bytes(),
+            target.as_bytes(),
+            mode,
+            1
+        ));
+        assert!(!edlibWithinDistanceRs(
+            query.as_bytes(),
+            target.as_bytes(),
+            mode,
+            0
+        ));
+    } // end of test_within_distance
+
+    #[test]
+    fn test_align_adaptive() {
+        let query = "ACCTCTG";
+        let target = "ACTCTGAAA";
+        let config = EdlibAlignConfigRs::default();
+        // real distance is 4
+        let align_res = edlibAlignAdaptiveRs(query.as_bytes(), target.as_bytes(), &config, 1, 16);
+        assert_eq!(align_res.status, EDLIB_STATUS_OK);
+        assert_eq!(align_res.editDistance, 4);
+        let align_res = edlibAlignAdaptiveRs(query.as_bytes(), target.as_bytes(), &config, 0, 5);
+        assert_eq!(align_res.editDistance, 4);
+        // ceiling below real distance
+        let align_res = edlibAlignAdaptiveRs(query.as_bytes(), target.as_bytes(), &config, 1, 3);
+        assert_eq!(align_res.editDistance, -1);
+    } // end of test_align_adaptive
+
+    #[test]
+    fn test_align_window() {
+        let query = "ACCTCTG";
+        let target = "TTTTTTTTTTACCTCTGTTTTTTTTTTACGTTTT";
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let full_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let window_res = edlibAlignWindowRs(query.as_bytes(), target.as_bytes(), 5..20, &config);
+        assert_eq!(window_res.editDistance, full_res.editDistance);
+        assert_eq!(window_res.start_locations(), full_res.start_locations());
+        assert_eq!(window_res.end_locations(), full_res.end_locations());
+        assert_eq!(window_res.start_locations().unwrap()[0], 10);
+        // window clamped to target length
+        let window_res = edlibAlignWindowRs(query.as_bytes(), target.as_bytes(), 5..100, &config);
+        assert_eq!(window_res.start_locations().unwrap()[0], 10);
+        // empty window
+        let window_res = edlibAlignWindowRs(query.as_bytes(), target.as_bytes(), 5..5, &config);
+        assert_eq!(window_res.editDistance, query.len() as i32);
+        assert_eq!(window_res.end_locations().unwrap()[0], 4);
+    } // end of test_align_window
+
+    #[test]
+    fn test_align_hw_chunked() {
+        let query = b"ACCTCTGAGT";
+        let mut target = vec![b'T'; 100];
+        // match straddling the chunk boundary at 40
+        target[35..45].copy_from_slice(query);
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let full_res = edlibAlignRs(query, &target, &config);
+        let align_res = edlibAlignHwChunked(query, &target[..], 40, 12, &config);
+        assert_eq!(align_res.status, EDLIB_STATUS_OK);
+        assert_eq!(align_res.editDistance, 0);
+        assert_eq!(align_res.end_locations().unwrap(), &vec![44]);
+        assert_eq!(align_res.start_locations().unwrap(), &vec![35]);
+        assert_eq!(align_res.alignment(), full_res.alignment());
+        // found in windows 2 and 3 (overlap of 30), reported once
+        let align_res = edlibAlignHwChunked(query, &target[..], 20, 30, &config);
+        assert_eq!(align_res.numLocations, 1);
+        assert_eq!(align_res.end_locations().unwrap(), &vec![44]);
+        // same distance and locations as the full alignment for an approximate match
+        target[40] = b'G';
+        let full_res = edlibAlignRs(query, &target, &config);
+        let align_res = edlibAlignHwChunked(query, &target[..], 16, 15, &config);
+        assert_eq!(align_res.editDistance, full_res.editDistance);
+        assert_eq!(align_res.end_locations(), full_res.end_locations());
+        assert_eq!(align_res.start_locations(), full_res.start_locations());
+    } // end of test_align_hw_chunked
+
+    #[test]
+    fn test_identity_threshold() {
+        let config = EdlibAlignConfigRs::default();
+        // 2 mismatches over 10 columns : identity 0.8
+        assert_eq!(
+            passes_identity_threshold(b"ACGTACGTAC", b"ACCTACGTGC", &config, 0.8),
+            Some(true)
+        );
+        // 3 mismatches over 10 columns : identity 0.7
+        assert_eq!(
+            passes_identity_threshold(b"ACGTACGTAC", b"ACCTAGGTGC", &config, 0.8),
+            Some(false)
+        );
+        // 1 insertion and 1 deletion over 11 columns
+        assert_eq!(
+            passes_identity_threshold(b"ACGTACGTAC", b"ACTACGTTAC", &config, 0.8),
+            Some(true)
+        );
+        let mut config = EdlibAlignConfigRs::default();
+        config.k = 1;
+        assert_eq!(
+            passes_identity_threshold(b"ACGTACGTAC", b"ACCTAGGTGC", &config, 0.8),
+            None
+        );
+    } // end of test_identity_threshold
+
+    #[test]
+    fn test_compute_alphabet() {
+        let pairs = [
+            ("ACCTCTG", "ACTCTGAAA"),
+            ("missing", "mississipi"),
+            ("", "ACGT"),
+            ("NNNN", "acgtACGT"),
+        ];
+        let equalities = ascii_case_insensitive_equalities();
+        let config = EdlibAlignConfigRs::case_insensitive(&equalities);
+        for (query, target) in pairs.iter() {
+            let alphabet = compute_alphabet(query.as_bytes(), target.as_bytes());
+            let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+            assert_eq!(alphabet.len(), align_res.alphabetLength as usize);
+        }
+        assert_eq!(compute_alphabet(b"missing", b"mississipi"), b"gimnps");
+        assert!(compute_alphabet(b"", b"").is_empty());
+    } // end of test_compute_alphabet
+
+    #[test]
+    fn test_empty_sequences() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        // empty query : all deletions
+        let align_res = edlibAlignRs(b"", b"ACGT", &config);
+        assert_eq!(align_res.status, EDLIB_STATUS_OK);
+        assert_eq!(align_res.editDistance, 4);
+        assert_eq!(align_res.end_locations().unwrap(), &vec![3]);
+        assert_eq!(align_res.start_locations().unwrap(), &vec![0]);
+        assert_eq!(align_res.alignment().unwrap(), &[2, 2, 2, 2]);
+        // empty target : all insertions
+        let align_res = edlibAlignRs(b"ACG", b"", &config);
+        assert_eq!(align_res.editDistance, 3);
+        assert_eq!(align_res.end_locations().unwrap(), &vec![-1]);
+        assert_eq!(align_res.alignment().unwrap(), &[1, 1, 1]);
+        // both empty
+        let align_res = edlibAlignRs(b"", b"", &config);
+        assert_eq!(align_res.status, EDLIB_STATUS_OK);
+        assert_eq!(align_res.editDistance, 0);
+        assert!(align_res.alignment().is_none());
+        // infix : empty query matches everywhere at no cost
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        let align_res = edlibAlignRs(b"", b"ACGT", &config);
+        assert_eq!(align_res.editDistance, 0);
+        assert_eq!(align_res.end_locations().unwrap(), &vec![-1]);
+        let align_res = edlibAlignRs(b"ACG", b"", &config);
+        assert_eq!(align_res.editDistance, 3);
+        assert_eq!(align_res.alignment().unwrap(), &[1, 1, 1]);
+        // distance only
+        assert_eq!(b"".edit_distance(b"ACGT"), Some(4));
+    } // end of test_empty_sequences
+
+    #[test]
+    fn test_result_display() {
+        let query = "missing";
+        let target = "mississipi";
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(format!("{}", align_res), "dist=2 locs=3 [0..4] cigar=5M2I");
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_DISTANCE;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(format!("{}", align_res), "dist=2 locs=3 [..4]");
+    } // end of test_result_display
+
+    #[test]
+    fn test_shw_with_start() {
+        let cases = [
+            ("ACCTCTG", "ACTCTGAAA"),
+            ("missing", "mississipi"),
+            ("TTACG", "ACGTTACG"),
+            ("AAGG", "CCTTAAGG"),
+        ];
+        let config = EdlibAlignConfigRs::prefix();
+        for (query, target) in cases.iter() {
+            let align_res = edlibAlignShwWithStart(query.as_bytes(), target.as_bytes(), &config);
+            let mut config_path = config;
+            config_path.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+            let path_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config_path);
+            assert_eq!(align_res.editDistance, path_res.editDistance);
+            assert_eq!(align_res.end_locations(), path_res.end_locations());
+            assert_eq!(align_res.start_locations(), path_res.start_locations());
+            assert!(align_res.start_locations().unwrap().iter().all(|&s| s == 0));
+            assert!(align_res.alignment().is_none());
+        }
+    } // end of test_shw_with_start
+
+    #[test]
+    fn test_align_suffix() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        // query matches only the end of target
+        let align_res = edlibAlignSuffixRs(b"CTGA", b"AAACCTGA", &config);
+        assert_eq!(align_res.editDistance, 0);
+        assert_eq!(align_res.start_locations(), Some(&[4][..]));
+        assert_eq!(align_res.end_locations(), Some(&[7][..]));
+        assert!(validate_alignment(b"CTGA", b"AAACCTGA", &align_res).is_ok());
+        // query matching the start of target is not free, contrary to prefix mode
+        let align_res = edlibAlignSuffixRs(b"AAAC", b"AAACCTGT", &config);
+        assert!(align_res.editDistance > 0);
+        assert!(validate_alignment(b"AAAC", b"AAACCTGT", &align_res).is_ok());
+        let prefix = edlibAlignRs(b"AAAC", b"AAACCTGT", &EdlibAlignConfigRs::prefix());
+        assert_eq!(prefix.editDistance, 0);
+        // an alignment with indels, checked against sequences
+        let align_res = edlibAlignSuffixRs(b"ACCTCTG", b"TTTTTTACTCTG", &config);
+        assert_eq!(align_res.editDistance, 1);
+        assert!(validate_alignment(b"ACCTCTG", b"TTTTTTACTCTG", &align_res).is_ok());
+        // start locations even with task distance
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_DISTANCE;
+        let align_res = edlibAlignSuffixRs(b"CTGA", b"AAACCTGA", &config);
+        assert_eq!(align_res.start_locations(), Some(&[4][..]));
+    } // end of test_align_suffix
+
+    #[test]
+    fn test_align_hw_anchored() {
+        let config = EdlibAlignConfigRs::default();
+        // only 4 bases of query can match
+        let weak = edlibAlignHwAnchored(b"AAAAGGGG", b"TTTTAAAATTTT", 5, &config);
+        assert_eq!(weak.status, EDLIB_STATUS_OK);
+        assert_eq!(weak.editDistance, -1);
+        assert!(weak.end_locations().is_none());
+        assert!(weak.alignment().is_none());
+        assert_eq!(weak.outcome(), AlignOutcome::ExceededK);
+        // a strong hit passes
+        let strong = edlibAlignHwAnchored(b"AAAAGGGG", b"TTTTAAAAGGCGTTTT", 5, &config);
+        assert_eq!(strong.editDistance, 1);
+        assert_eq!(strong.start_locations(), Some(&[4][..]));
+        assert_eq!(strong.paf_match_block(), Some((7, 8)));
+        // the weak hit is kept with a lower threshold
+        let weak = edlibAlignHwAnchored(b"AAAAGGGG", b"TTTTAAAATTTT", 4, &config);
+        assert_eq!(weak.editDistance, 4);
+    } // end of test_align_hw_anchored
+
+    #[test]
+    fn test_locations_1based() {
+        let query = "missing";
+        let target = "mississipi";
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.end_locations().unwrap()[0], 4);
+        assert_eq!(align_res.end_locations_1based().unwrap()[0], 5);
+        assert!(align_res.start_locations_1based().is_none());
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.start_locations_1based().unwrap()[0], 1);
+        assert_eq!(align_res.start_locations().unwrap()[0], 0);
+    } // end of test_locations_1based
+
+    #[test]
+    fn test_from_raw() {
+        let query = "missing";
+        let target = "mississipi";
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let res_c = alignC(query.as_bytes(), target.as_bytes(), &config);
+        let align_res = unsafe { EdlibAlignResultRs::from_raw(&res_c) };
+        assert_eq!(align_res.numLocations, res_c.numLocations as usize);
+        unsafe { edlibFreeAlignResult(res_c) };
+        let expected = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.status, EDLIB_STATUS_OK);
+        assert_eq!(align_res.editDistance, expected.editDistance);
+        assert_eq!(align_res.end_locations(), expected.end_locations());
+        assert_eq!(align_res.start_locations(), expected.start_locations());
+        assert_eq!(align_res.alignment(), expected.alignment());
+        assert_eq!(align_res.alphabetLength, expected.alphabetLength);
+        // a null alignment pointer with a length is an error
+        let mut res_c = alignC(query.as_bytes(), target.as_bytes(), &config);
+        let alignment = res_c.alignment;
+        res_c.alignment = std::ptr::null_mut();
+        let align_res = unsafe { EdlibAlignResultRs::from_raw(&res_c) };
+        assert_eq!(align_res.status, EDLIB_STATUS_ERROR);
+        res_c.alignment = alignment;
+        unsafe { edlibFreeAlignResult(res_c) };
+    } // end of test_from_raw
+
+    #[test]
+    fn test_align_best_location() {
+        let query = "ACGT";
+        let config = EdlibAlignConfigRs::infix();
+        // two exact occurrences
+        let target = "TTACGTTTTTACGTTT";
+        let left = align_best_location(
+            query.as_bytes(),
+            target.as_bytes(),
+            &config,
+            LocationPolicy::Leftmost,
+        );
+        let right = align_best_location(
+            query.as_bytes(),
+            target.as_bytes(),
+            &config,
+            LocationPolicy::Rightmost,
+        );
+        assert_eq!(left.numLocations, 2);
+        assert_eq!(left.start_locations().unwrap()[0], 2);
+        assert_eq!(left.end_locations().unwrap()[0], 5);
+        assert_eq!(right.start_locations().unwrap()[0], 10);
+        assert_eq!(right.end_locations().unwrap()[0], 13);
+        assert_eq!(right.start_locations().unwrap()[1], 2);
+        assert_eq!(right.alignment().unwrap(), &[0, 0, 0, 0]);
+        assert!(validate_alignment(query.as_bytes(), target.as_bytes(), &right).is_ok());
+        // two locations ending at the same position, the shortest drops the leading mismatch
+        let query = "AACGT";
+        let target = "TTCACGTTT";
+        let shortest = align_best_location(
+            query.as_bytes(),
+            target.as_bytes(),
+            &config,
+            LocationPolicy::Shortest,
+        );
+        let locations = shortest.locations();
+        let (end, start) = locations[0];
+        assert!(locations
+            .iter()
+            .all(|&(e, s)| e - s.unwrap() >= end - start.unwrap()));
+        assert!(validate_alignment(query.as_bytes(), target.as_bytes(), &shortest).is_ok());
+    } // end of test_align_best_location
+
+    #[test]
+    fn test_cluster_locations() {
+        let result = EdlibAlignResultRs {
+            endLocations: Some(vec![10, 11, 50]),
+            startLocations: Some(vec![5, 7, 45]),
+            numLocations: 3,
+            ..Default::default()
+        };
+        assert_eq!(cluster_locations(&result, 2), vec![(7, 11), (45, 50)]);
+        assert_eq!(cluster_locations(&result, 0).len(), 3);
+        assert_eq!(cluster_locations(&result, 100), vec![(7, 11)]);
+        // without start locations
+        let result = EdlibAlignResultRs {
+            endLocations: Some(vec![10, 11, 50]),
+            numLocations: 3,
+            ..Default::default()
+        };
+        assert_eq!(cluster_locations(&result, 2), vec![(-1, 10), (-1, 50)]);
+        assert!(cluster_locations(&EdlibAlignResultRs::default(), 2).is_empty());
+    } // end of test_cluster_locations
+
+    #[test]
+    fn test_check_ascii() {
+        assert!(check_ascii(b"ACGT", b"acgt NN").is_ok());
+        assert_eq!(
+            check_ascii("caf\u{e9}".as_bytes(), b"cafe"),
+            Err(NonAsciiError::Query {
+                position: 3,
+                byte: 0xc3
+            })
+        );
+        assert_eq!(
+            check_ascii(b"cafe", "un caf\u{e9}".as_bytes()),
+            Err(NonAsciiError::Target {
+                position: 6,
+                byte: 0xc3
+            })
+        );
+        let config = EdlibAlignConfigRs::default();
+        let align_res = edlibAlignAsciiRs(b"ACCTCTG", b"ACTCTGAAA", &config).unwrap();
+        assert_eq!(align_res.editDistance, 4);
+        assert!(edlibAlignAsciiRs(b"ACCTCTG", "\u{e9}".as_bytes(), &config).is_err());
+    } // end of test_check_ascii
+
+    #[test]
+    fn test_align_with_timeout() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignWithTimeout(
+            b"missing",
+            b"mississipi",
+            &config,
+            std::time::Duration::from_secs(60),
+        )
+        .unwrap();
+        let expected = edlibAlignRs(b"missing", b"mississipi", &config);
+        assert_eq!(align_res.editDistance, expected.editDistance);
+        assert_eq!(align_res.alignment(), expected.alignment());
+    } // end of test_align_with_timeout
+
+    #[test]
+    fn test_align_safe() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignSafe(b"missing", b"mississipi", &config).unwrap();
+        assert_eq!(align_res.editDistance, 2);
+        assert!(edlibAlignSafe(b"", b"", &config).is_ok());
+        // estimation overflow is reported as out of memory
+        assert!(estimatedEdlibMemory(usize::MAX, 1).is_none());
+        assert!(estimatedEdlibMemory(100, 1000).unwrap() > 1024 * 1024);
+    } // end of test_align_safe
+
+    #[test]
+    fn test_align_with_remap() {
+        let mut remap = [0u8; 256];
+        for (i, r) in remap.iter_mut().enumerate() {
+            *r = i as u8;
+        }
+        let config = EdlibAlignConfigRs::default();
+        assert_eq!(
+            align_with_remap(b"AB", b"AA", &remap, &config).editDistance,
+            1
+        );
+        remap[b'B' as usize] = b'A';
+        let align_res = align_with_remap(b"AB", b"AA", &remap, &config);
+        assert_eq!(align_res.editDistance, 0);
+        assert_eq!(align_res.alphabetLength, 1);
+    } // end of test_align_with_remap
+
+    #[test]
+    fn test_longest_match_run() {
+        let mut align_res = EdlibAlignResultRs::default();
+        assert!(align_res.longest_match_run().is_none());
+        align_res.alignment = Some(vec![0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 2]);
+        assert_eq!(align_res.longest_match_run(), Some((3, 3)));
+        align_res.alignment = Some(vec![1, 0, 0, 3, 0, 0, 0, 0]);
+        assert_eq!(align_res.longest_match_run(), Some((4, 4)));
+        align_res.alignment = Some(vec![1, 3, 2]);
+        assert_eq!(align_res.longest_match_run(), Some((0, 0)));
+        //
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(b"missing", b"mississipi", &config);
+        assert_eq!(align_res.longest_match_run(), Some((0, 5)));
+    } // end of test_longest_match_run
+
+    #[test]
+    fn test_mismatch_positions() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(b"ACCTCTG", b"TTTTTACGTCTGTTT", &config);
+        assert_eq!(align_res.editDistance, 1);
+        assert_eq!(align_res.mismatch_positions(0), Some(vec![7]));
+        assert_eq!(align_res.mismatch_positions(1000), Some(vec![1007]));
+        assert!(align_res.mismatch_positions(-1).is_none());
+        // no path
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(b"ACCTCTG", b"TTTTTACGTCTGTTT", &config);
+        assert!(align_res.mismatch_positions(0).is_none());
+    } // end of test_mismatch_positions
+
+    #[test]
+    fn test_paf_match_block() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(b"missing", b"mississipi", &config);
+        assert_eq!(align_res.paf_match_block(), Some((5, 7)));
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(b"missing", b"mississipi", &config);
+        assert!(align_res.paf_match_block().is_none());
+    } // end of test_paf_match_block
+
+    #[test]
+    fn test_query_identity() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        // 4 deletions in the middle : 8 matches over 12 columns, but over 8 query positions
+        let align_res = edlibAlignRs(b"ACGTACGT", b"ACGTTTTTACGT", &config);
+        assert_eq!(align_res.editDistance, 4);
+        assert_eq!(align_res.query_identity(8), Some(1.));
+        let (matches, block) = align_res.paf_match_block().unwrap();
+        let block_identity = matches as f64 / block as f64;
+        assert!(align_res.query_identity(8).unwrap() > block_identity);
+        // wrong query length
+        assert!(align_res.query_identity(7).is_none());
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(b"ACGTACGT", b"ACGTTTTTACGT", &config);
+        assert!(align_res.query_identity(8).is_none());
+    } // end of test_query_identity
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_snake_case_accessors() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(b"missing", b"mississipi", &config);
+        assert_eq!(align_res.edit_distance(), align_res.editDistance);
+        assert_eq!(align_res.status(), align_res.status);
+        assert_eq!(align_res.alphabet_length(), align_res.alphabetLength);
+        assert_eq!(align_res.end_locations(), align_res.endLocations.as_deref());
+        assert_eq!(
+            align_res.start_locations(),
+            align_res.startLocations.as_deref()
+        );
+        assert_eq!(align_res.alignment(), align_res.alignment.as_deref());
+        // same values as the deprecated getters
+        assert_eq!(align_res.edit_distance(), align_res.getDistance());
+        assert_eq!(
+            align_res.end_locations(),
+            align_res.getEndLocations().map(|v| &v[..])
+        );
+        assert_eq!(
+            align_res.start_locations(),
+            align_res.getStartLocations().map(|v| &v[..])
+        );
+        assert_eq!(
+            align_res.alignment(),
+            align_res.getAlignment().map(|v| &v[..])
+        );
+    } // end of test_snake_case_accessors
+
+    #[test]
+    fn test_into_c_arrays() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(b"missing", b"mississipi", &config);
+        let expected = align_res.clone();
+        let (ends, starts, alignment) = align_res.into_c_arrays();
+        // through raw pointers as a C caller would get them, then back to Rust
+        let (ends_len, starts_len, alignment_len) = (ends.len(), starts.len(), alignment.len());
+        let ends_ptr = Box::into_raw(ends) as *mut i32;
+        let starts_ptr = Box::into_raw(starts) as *mut i32;
+        let alignment_ptr = Box::into_raw(alignment) as *mut u8;
+        let ends = unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ends_ptr, ends_len)) };
+        let starts =
+            unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(starts_ptr, starts_len)) };
+        let alignment = unsafe {
+            Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+                alignment_ptr,
+                alignment_len,
+            ))
+        };
+        assert_eq!(Some(&ends[..]), expected.end_locations());
+        assert_eq!(Some(&starts[..]), expected.start_locations());
+        assert_eq!(Some(&alignment[..]), expected.alignment());
+        //
+        let (ends, starts, alignment) = EdlibAlignResultRs::default().into_c_arrays();
+        assert!(ends.is_empty() && starts.is_empty() && alignment.is_empty());
+    } // end of test_into_c_arrays
+
+    #[test]
+    fn test_best_location() {
+        let query = "missing";
+        let target = "mississipi";
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.best_location(), Some((4, Some(0), 2)));
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_DISTANCE;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.best_location(), Some((4, None, 2)));
+        config.k = 1;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert!(align_res.best_location().is_none());
+        assert!(EdlibAlignResultRs::default().best_location().is_none());
+    } // end of test_best_location
+
+    #[test]
+    fn test_outcome() {
+        let query = "ACCTCTG";
+        let target = "ACTCTGAAA";
+        let mut config = EdlibAlignConfigRs::default();
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.outcome(), AlignOutcome::Found(4));
+        config.k = 3;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.outcome(), AlignOutcome::ExceededK);
+        // edlib errors cannot be triggered with valid Rust enums, set the status as edlib would
+        let mut align_res = EdlibAlignResultRs::default();
+        align_res.status = EDLIB_STATUS_ERROR;
+        assert_eq!(align_res.outcome(), AlignOutcome::Error);
+    } // end of test_outcome
+
+    #[cfg(feature = "logging")]
+    #[test]
+    fn test_align_logging() {
+        use std::sync::Mutex;
+        // stores messages of all threads, other tests may log concurrently
+        struct CaptureLogger {
+            lines: Mutex<Vec<String>>,
+        }
+        impl log::Log for CaptureLogger {
+            fn enabled(&self, _metadata: &log::Metadata) -> bool {
+                true
+            }
+            fn log(&self, record: &log::Record) {
+                self.lines
+                    .lock()
+                    .unwrap()
+                    .push(format!("{}", record.args()));
+            }
+            fn flush(&self) {}
+        }
+        static LOGGER: CaptureLogger = CaptureLogger {
+            lines: Mutex::new(Vec::new()),
+        };
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+        let align_res = edlibAlignRs(
+            b"ACCTCTGAGTCA",
+            b"ACTCTGAAA",
+            &EdlibAlignConfigRs::default(),
+        );
+        assert_eq!(align_res.status, EDLIB_STATUS_OK);
+        let lines = LOGGER.lines.lock().unwrap();
+        assert!(lines
+            .iter()
+            .any(|l| l.contains("query len 12 target len 9") && l.contains("EDLIB_MODE_NW")));
+    } // end of test_align_logging
+
+    #[test]
+    fn test_align_ext() {
+        let query = "ACCTCTG";
+        let target = "ACTCTGAAA";
+        assert_eq!(query.edit_distance(target), Some(4));
+        assert_eq!(query.as_bytes().edit_distance(target.as_bytes()), Some(4));
+        assert_eq!(query.as_bytes().edit_distance(target), Some(4));
+        let config = EdlibAlignConfigRs::prefix();
+        let align_res = query.align(target, &config);
+        assert_eq!(align_res.editDistance, 1);
+        let align_res = query.as_bytes().align(target.as_bytes(), &config);
+        assert_eq!(align_res.editDistance, 1);
+    } // end of test_align_ext
+
+    #[test]
+    fn test_align_into() {
+        let query = "missing";
+        let target = "mississipi";
+        //
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let mut out = EdlibAlignResultRs::default();
+        edlibAlignInto(query.as_bytes(), target.as_bytes(), &config, &mut out);
+        assert_eq!(format!("{:?}", out), format!("{:?}", align_res));
+        // reuse
+        let capacity = out.alignment.as_ref().unwrap().capacity();
+        let ptr = out.alignment.as_ref().unwrap().as_ptr();
+        for _ in 0..100 {
+            edlibAlignInto(query.as_bytes(), target.as_bytes(), &config, &mut out);
+            assert_eq!(out.alignment.as_ref().unwrap().capacity(), capacity);
+            assert_eq!(out.alignment.as_ref().unwrap().as_ptr(), ptr);
+        }
+        assert_eq!(format!("{:?}", out), format!("{:?}", align_res));
+        // fields not computed are reset
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_DISTANCE;
+        edlibAlignInto(query.as_bytes(), target.as_bytes(), &config, &mut out);
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(format!("{:?}", out), format!("{:?}", align_res));
+    } // end of test_align_into
+
+    #[test]
+    fn test_locations_hw() {
+        let query = "ACGT";
+        let target = "ACGTTTACGT";
+        //
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.status, EDLIB_STATUS_OK);
+        assert_eq!(align_res.editDistance, 0);
+        let locations = align_res.locations();
+        assert_eq!(locations.len(), align_res.numLocations);
+        assert_eq!(locations, vec![(3, Some(0)), (9, Some(6))]);
+        // without start locations
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_DISTANCE;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.locations(), vec![(3, None), (9, None)]);
+    } // end of test_locations_hw
+
+    #[test]
+    fn test_result_predicates() {
+        let query = "missing";
+        let target = "mississipi";
+        //
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert!(!align_res.has_path());
+        assert!(!align_res.has_locations());
+        assert!(align_res.num_locations() > 0);
+        //
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert!(!align_res.has_path());
+        assert!(align_res.has_locations());
+        //
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert!(align_res.has_path());
+        assert!(align_res.has_locations());
+        assert_eq!(align_res.num_locations(), align_res.numLocations);
+    } // end of test_result_predicates
+
+    #[test]
+    fn test_config_presets() {
+        let config = EdlibAlignConfigRs::global();
+        assert!(matches!(config.mode, EdlibAlignModeRs::EDLIB_MODE_NW));
+        let config = EdlibAlignConfigRs::prefix();
+        assert!(matches!(config.mode, EdlibAlignModeRs::EDLIB_MODE_SHW));
+        let config = EdlibAlignConfigRs::infix();
+        assert!(matches!(config.mode, EdlibAlignModeRs::EDLIB_MODE_HW));
+        assert_eq!(config.k, -1);
+        assert!(matches!(config.task, EdlibAlignTaskRs::EDLIB_TASK_DISTANCE));
+        assert!(config.additionalequalities.is_empty());
+    } // end of test_config_presets
+
+    #[test]
+    fn test_enum_try_from() {
+        for mode in [
+            EdlibAlignModeRs::EDLIB_MODE_NW,
+            EdlibAlignModeRs::EDLIB_MODE_SHW,
+            EdlibAlignModeRs::EDLIB_MODE_HW,
+        ] {
+            let back = EdlibAlignModeRs::try_from(mode as u32).unwrap();
+            assert_eq!(back as u32, mode as u32);
+        }
+        for task in [
+            EdlibAlignTaskRs::EDLIB_TASK_DISTANCE,
+            EdlibAlignTaskRs::EDLIB_TASK_LOC,
+            EdlibAlignTaskRs::EDLIB_TASK_PATH,
+        ] {
+            let back = EdlibAlignTaskRs::try_from(task as u32).unwrap();
+            assert_eq!(back as u32, task as u32);
+        }
+        for format in [
+            EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD,
+            EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED,
+        ] {
+            let back = EdlibCigarFormatRs::try_from(format as u32).unwrap();
+            assert_eq!(back as u32, format as u32);
+        }
+        for op in [
+            EdlibEdopRs::EDLIB_EDOP_MATCH,
+            EdlibEdopRs::EDLIB_EDOP_INSERT,
+            EdlibEdopRs::EDLIB_EDOP_DELETE,
+            EdlibEdopRs::EDLIB_EDOP_MISMATCH,
+        ] {
+            let back = EdlibEdopRs::try_from(op as u32).unwrap();
+            assert_eq!(back as u32, op as u32);
+        }
+        // C values
+        assert_eq!(
+            EdlibAlignModeRs::try_from(EdlibAlignMode_EDLIB_MODE_HW).unwrap() as u32,
+            EdlibAlignModeRs::EDLIB_MODE_HW as u32
+        );
+        assert_eq!(
+            EdlibEdopRs::try_from(EDLIB_EDOP_DELETE).unwrap() as u32,
+            EdlibEdopRs::EDLIB_EDOP_DELETE as u32
+        );
+        // unknown value
+        let err = EdlibAlignTaskRs::try_from(3).unwrap_err();
+        assert_eq!(
+            err,
+            EdlibEnumError {
+                name: "EdlibAlignTaskRs",
+                value: 3
+            }
+        );
+        assert!(EdlibEdopRs::try_from(4).is_err());
+    } // end of test_enum_try_from
+
+    #[test]
+    fn test_enum_bindgen_values() {
+        assert_eq!(
+            EdlibAlignModeRs::EDLIB_MODE_NW as EdlibAlignMode,
+            EdlibAlignMode_EDLIB_MODE_NW
+        );
+        assert_eq!(
+            EdlibAlignModeRs::EDLIB_MODE_SHW as EdlibAlignMode,
+            EdlibAlignMode_EDLIB_MODE_SHW
+        );
+        assert_eq!(
+            EdlibAlignModeRs::EDLIB_MODE_HW as EdlibAlignMode,
+            EdlibAlignMode_EDLIB_MODE_HW
+        );
+        assert_eq!(
+            EdlibAlignTaskRs::EDLIB_TASK_DISTANCE as EdlibAlignTask,
+            EdlibAlignTask_EDLIB_TASK_DISTANCE
+        );
+        assert_eq!(
+            EdlibAlignTaskRs::EDLIB_TASK_LOC as EdlibAlignTask,
+            EdlibAlignTask_EDLIB_TASK_LOC
+        );
+        assert_eq!(
+            EdlibAlignTaskRs::EDLIB_TASK_PATH as EdlibAlignTask,
+            EdlibAlignTask_EDLIB_TASK_PATH
+        );
+        assert_eq!(
+            EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD as EdlibCigarFormat,
+            EdlibCigarFormat_EDLIB_CIGAR_STANDARD
+        );
+        assert_eq!(
+            EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED as EdlibCigarFormat,
+            EdlibCigarFormat_EDLIB_CIGAR_EXTENDED
+        );
+        assert_eq!(EdlibEdopRs::EDLIB_EDOP_MATCH as u32, EDLIB_EDOP_MATCH);
+        assert_eq!(EdlibEdopRs::EDLIB_EDOP_INSERT as u32, EDLIB_EDOP_INSERT);
+        assert_eq!(EdlibEdopRs::EDLIB_EDOP_DELETE as u32, EDLIB_EDOP_DELETE);
+        assert_eq!(EdlibEdopRs::EDLIB_EDOP_MISMATCH as u32, EDLIB_EDOP_MISMATCH);
+    } // end of test_enum_bindgen_values
+
+    #[test]
+    fn test_edlib_version() {
+        let version = edlib_version();
+        assert!(!version.is_empty());
+        let numbers: Vec<&str> = version.split('.').collect();
+        assert_eq!(numbers.len(), 3);
+        for n in numbers {
+            assert!(n.parse::<u32>().is_ok(), "bad version number {}", version);
+        }
+    } // end of test_edlib_version
+
+    fn _assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_send_sync() {
+        _assert_send_sync::<EdlibAlignResultRs>();
+        _assert_send_sync::<EdlibAlignConfigRs>();
+        _assert_send_sync::<EdlibAlignModeRs>();
+        _assert_send_sync::<EdlibAlignTaskRs>();
+        _assert_send_sync::<EdlibEqualityPairRs>();
+    } // end of test_send_sync
+
+    #[test]
+    fn test_align_multithread() {
+        let query = "missing";
+        let target = "mississipi";
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                std::thread::spawn(move || {
+                    let mut config = EdlibAlignConfigRs::default();
+                    config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+                    config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+                    for _ in 0..500 {
+                        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+                        assert_eq!(align_res.status, EDLIB_STATUS_OK);
+                        assert_eq!(align_res.editDistance, 2);
+                        assert_eq!(align_res.alignment().unwrap(), &vec![0, 0, 0, 0, 0, 1, 1]);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    } // end of test_align_multithread
+
+    #[test]
+    fn test_align_to_cigar() {
+        let query = "missing";
+        let target = "mississipi";
+        //
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        let cigar = edlibAlignToCigarRs(
+            query.as_bytes(),
+            target.as_bytes(),
+            &config,
+            EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD,
+        );
+        assert_eq!(cigar, Some(String::from("5M2I")));
+        // distance is 2, so k = 1 gives no path
+        config.k = 1;
+        let cigar = edlibAlignToCigarRs(
+            query.as_bytes(),
+            target.as_bytes(),
+            &config,
+            EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD,
+        );
+        assert!(cigar.is_none());
+    } // end of test_align_to_cigar
+
+    #[test]
+    fn test_cigars_for_all_locations() {
+        let query = "ACGG";
+        let target = "ACCTTTTTTACGTAAAAAACGAT";
+        //
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.editDistance, 1);
+        assert_eq!(
+            align_res.locations(),
+            vec![(11, Some(9)), (12, Some(9)), (20, Some(18)), (21, Some(18))]
+        );
+        let cigars = cigars_for_all_locations(
+            query.as_bytes(),
+            target.as_bytes(),
+            &align_res,
+            &config,
+            EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED,
+        );
+        assert_eq!(cigars, vec!["3=1I", "3=1X", "3=1I", "3=1X"]);
+        // without start locations
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_DISTANCE;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let cigars = cigars_for_all_locations(
+            query.as_bytes(),
+            target.as_bytes(),
+            &align_res,
+            &config,
+            EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED,
+        );
+        assert!(cigars.is_empty());
+        // empty target : start 0, end -1, no target span to realign
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), b"", &config);
+        assert_eq!(align_res.locations(), vec![(-1, Some(0))]);
+        let cigars = cigars_for_all_locations(
+            query.as_bytes(),
+            b"",
+            &align_res,
+            &config,
+            EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED,
+        );
+        assert!(cigars.is_empty());
+        // additional equalities are used for the realignment
+        let equalities = [EdlibEqualityPairRs::new(b'G', b'T')];
+        config.additionalequalities = &equalities;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.editDistance, 0);
+        let cigars = cigars_for_all_locations(
+            query.as_bytes(),
+            target.as_bytes(),
+            &align_res,
+            &config,
+            EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED,
+        );
+        assert_eq!(cigars, vec!["4="]);
+    } // end of test_cigars_for_all_locations
 } // mod tests
@@ -0,0 +1,883 @@
+//! Helpers working directly on alignment paths, as found in EdlibAlignResultRs.alignment.
+//! A path is a sequence of opcodes :
+//!     * 0 stands for match.
+//!     * 1 stands for insertion to target (query only residue).
+//!     * 2 stands for insertion to query (target only residue).
+//!     * 3 stands for mismatch.
+
+use crate::*;
+use std::fmt;
+
+/// Test if opcode is a gap, i.e insertion to target or to query.
+fn is_gap(op: u8) -> bool {
+    op == EdlibEdopRs::EDLIB_EDOP_INSERT as u8 || op == EdlibEdopRs::EDLIB_EDOP_DELETE as u8
+}
+
+/// Removes gap opcodes (1 or 2) at the very start and end of an alignment path.
+/// Internal gaps are never removed.
+/// Returns the trimmed path with the number of leading and trailing opcodes removed,
+/// so callers can adjust query and target coordinates.
+pub fn trim_flanking_gaps(alignment: &[u8]) -> (&[u8], usize, usize) {
+    let leading = alignment.iter().take_while(|&&op| is_gap(op)).count();
+    let trailing = alignment[leading..]
+        .iter()
+        .rev()
+        .take_while(|&&op| is_gap(op))
+        .count();
+    (
+        &alignment[leading..alignment.len() - trailing],
+        leading,
+        trailing,
+    )
+}
+
+/// Reverses an alignment path, swapping insertions to target (1) and insertions to query (2).
+/// Matches and mismatches are unchanged.
+/// If alignment aligns query to target, the result aligns target to query read from end to start,
+/// i.e reversed target to reversed query. Reversing twice gives back the original path.
+pub fn reverse_alignment(alignment: &[u8]) -> Vec<u8> {
+    alignment
+        .iter()
+        .rev()
+        .map(|&op| match op {
+            1 => 2,
+            2 => 1,
+            _ => op,
+        })
+        .collect()
+}
+
+/// Error returned by validate_alignment, identifying the first inconsistency found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlignmentMismatch {
+    /// The result has no alignment path or no locations.
+    NoAlignment,
+    /// Unknown opcode at given column.
+    InvalidOp { column: usize, op: u8 },
+    /// The walk goes past the end of query or target at given column.
+    OutOfRange { column: usize },
+    /// A match opcode at given column is on different residues.
+    NotEqual {
+        column: usize,
+        query_pos: usize,
+        target_pos: usize,
+    },
+    /// A mismatch opcode at given column is on equal residues.
+    NotDifferent {
+        column: usize,
+        query_pos: usize,
+        target_pos: usize,
+    },
+    /// The walk does not consume the whole query or does not end at endLocations\[0\].
+    /// Holds the number of query and target residues consumed.
+    LengthMismatch {
+        query_consumed: usize,
+        target_consumed: usize,
+    },
+}
+
+impl fmt::Display for AlignmentMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlignmentMismatch::NoAlignment => write!(f, "no alignment path or locations in result"),
+            AlignmentMismatch::InvalidOp { column, op } => {
+                write!(f, "invalid opcode {} at column {}", op, column)
+            }
+            AlignmentMismatch::OutOfRange { column } => {
+                write!(f, "alignment goes past sequence end at column {}", column)
+            }
+            AlignmentMismatch::NotEqual {
+                column,
+                query_pos,
+                target_pos,
+            } => write!(
+                f,
+                "match at column {} on different residues (query {}, target {})",
+                column, query_pos, target_pos
+            ),
+            AlignmentMismatch::NotDifferent {
+                column,
+                query_pos,
+                target_pos,
+            } => write!(
+                f,
+                "mismatch at column {} on equal residues (query {}, target {})",
+                column, query_pos, target_pos
+            ),
+            AlignmentMismatch::LengthMismatch {
+                query_consumed,
+                target_consumed,
+            } => write!(
+                f,
+                "alignment consumes {} query and {} target residues, not matching sequences and locations",
+                query_consumed, target_consumed
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AlignmentMismatch {}
+
+/// Checks that the alignment path of result really aligns query to target : the path is replayed
+/// from position 0 in query and startLocations\[0\] in target, match opcodes must be on equal residues,
+/// mismatch opcodes on different ones, and the walk must consume the whole query and end at endLocations\[0\].
+/// Residues are compared byte to byte, additional equalities of the configuration are not taken into account
+/// so an alignment computed with equalities can be rejected : use validate_alignment_with_equalities for these.
+/// This is a testing aid to detect a path paired with wrong sequences.
+pub fn validate_alignment(
+    query: &[u8],
+    target: &[u8],
+    result: &EdlibAlignResultRs,
+) -> Result<(), AlignmentMismatch> {
+    validate_alignment_with_equalities(query, target, result, &Equalities::default())
+}
+
+/// Same as validate_alignment but residues are compared with equalities, which should be built from
+/// the additional equalities of the configuration used to compute result.
+pub fn validate_alignment_with_equalities(
+    query: &[u8],
+    target: &[u8],
+    result: &EdlibAlignResultRs,
+    equalities: &Equalities,
+) -> Result<(), AlignmentMismatch> {
+    let alignment = result.alignment().ok_or(AlignmentMismatch::NoAlignment)?;
+    let start = *result
+        .start_locations()
+        .and_then(|s| s.first())
+        .ok_or(AlignmentMismatch::NoAlignment)?;
+    let end = *result
+        .end_locations()
+        .and_then(|e| e.first())
+        .ok_or(AlignmentMismatch::NoAlignment)?;
+    let mut query_pos = 0usize;
+    let mut target_pos = start as usize;
+    for (column, &op) in alignment.iter().enumerate() {
+        let advance_query = op != 2;
+        let advance_target = op != 1;
+        if op > 3 {
+            return Err(AlignmentMismatch::InvalidOp { column, op });
+        }
+        if (advance_query && query_pos >= query.len())
+            || (advance_target && target_pos >= target.len())
+        {
+            return Err(AlignmentMismatch::OutOfRange { column });
+        }
+        let equal = || equalities.equals(query[query_pos], target[target_pos]);
+        if op == 0 && !equal() {
+            return Err(AlignmentMismatch::NotEqual {
+                column,
+                query_pos,
+                target_pos,
+            });
+        }
+        if op == 3 && equal() {
+            return Err(AlignmentMismatch::NotDifferent {
+                column,
+                query_pos,
+                target_pos,
+            });
+        }
+        query_pos += advance_query as usize;
+        target_pos += advance_target as usize;
+    }
+    let target_consumed = target_pos - start as usize;
+    if query_pos != query.len() || target_consumed as i64 != end as i64 - start as i64 + 1 {
+        return Err(AlignmentMismatch::LengthMismatch {
+            query_consumed: query_pos,
+            target_consumed,
+        });
+    }
+    Ok(())
+}
+
+/// Finds the alignment columns where a match opcode joins two different bytes, which are equal only through
+/// one of the equality pairs (in either order, as edlib makes pairs symmetric).
+/// This tells which matches of an alignment computed with additionalequalities (wildcards, ambiguity codes...)
+/// are not exact. equalities should be the additionalequalities of the configuration used.
+/// Returns the sorted column indices in alignment, or None if result has no alignment path or start location.
+pub fn matches_via_equalities(
+    query: &[u8],
+    target: &[u8],
+    result: &EdlibAlignResultRs,
+    equalities: &[EdlibEqualityPairRs],
+) -> Option<Vec<usize>> {
+    let alignment = result.alignment()?;
+    let pairs = result.coordinate_pairs(0)?;
+    let is_equality = |a: u8, b: u8| {
+        equalities.iter().any(|pair| {
+            (pair.first() == a && pair.second() == b) || (pair.first() == b && pair.second() == a)
+        })
+    };
+    let columns = alignment
+        .iter()
+        .zip(pairs.iter())
+        .enumerate()
+        .filter_map(|(column, (&op, &(query_pos, target_pos)))| {
+            if op != EdlibEdopRs::EDLIB_EDOP_MATCH as u8 {
+                return None;
+            }
+            let q = *query.get(query_pos?)?;
+            let t = *target.get(target_pos?)?;
+            if q != t && is_equality(q, t) {
+                Some(column)
+            } else {
+                None
+            }
+        })
+        .collect();
+    Some(columns)
+}
+
+/// Computes how many times each target position is covered by the alignments of several queries.
+/// Each element of results is the start location in target of an alignment and its result
+/// (computed with task EDLIB_TASK_PATH, others are skipped).
+/// Each target position touched by a match, mismatch or insertion to query (2) opcode is incremented once
+/// per alignment. Insertions to target (1) advance the query only and do not touch the target.
+/// The returned Vec has length target_len, positions past target_len are ignored.
+pub fn target_coverage(target_len: usize, results: &[(i32, &EdlibAlignResultRs)]) -> Vec<u32> {
+    let mut coverage = vec![0u32; target_len];
+    for &(start, result) in results {
+        let alignment = match result.alignment() {
+            Some(alignment) => alignment,
+            None => continue,
+        };
+        let mut target_pos = start.max(0) as usize;
+        for &op in alignment {
+            if op == EdlibEdopRs::EDLIB_EDOP_INSERT as u8 {
+                continue;
+            }
+            if let Some(c) = coverage.get_mut(target_pos) {
+                *c += 1;
+            }
+            target_pos += 1;
+        }
+    }
+    coverage
+}
+
+/// Rescores an alignment path with a linear scheme : each match adds match_s, each mismatch mismatch_s
+/// and each insertion or deletion gap_s. edlib itself only minimizes the unit cost edit distance,
+/// so the path is the one found by edlib, not the best path under these weights.
+/// Returns None if alignment is empty (as when no path was computed) or contains an opcode greater than 3.
+pub fn weighted_score(alignment: &[u8], match_s: i32, mismatch_s: i32, gap_s: i32) -> Option<i32> {
+    if alignment.is_empty() {
+        return None;
+    }
+    let mut score = 0i32;
+    for &op in alignment {
+        score += match op {
+            0 => match_s,
+            3 => mismatch_s,
+            1 | 2 => gap_s,
+            _ => return None,
+        };
+    }
+    Some(score)
+}
+
+/// Rescores an alignment path with affine gap penalties : matches add match_s, mismatches mismatch_s,
+/// each gap run adds gap_open once and each gapped position gap_extend, so a run of length l adds
+/// gap_open + l * gap_extend. A run of insertions (1) directly followed by a run of deletions (2),
+/// or the reverse, counts as two gaps, each paying gap_open.
+/// As for weighted_score the path is the one found by edlib, not the best under affine scoring.
+/// Returns None if alignment is empty (as when no path was computed) or contains an opcode greater than 3.
+pub fn affine_score(
+    alignment: &[u8],
+    match_s: i32,
+    mismatch_s: i32,
+    gap_open: i32,
+    gap_extend: i32,
+) -> Option<i32> {
+    if alignment.is_empty() {
+        return None;
+    }
+    let mut score = 0i32;
+    let mut previous: Option<u8> = None;
+    for &op in alignment {
+        score += match op {
+            0 => match_s,
+            3 => mismatch_s,
+            1 | 2 if previous == Some(op) => gap_extend,
+            1 | 2 => gap_open + gap_extend,
+            _ => return None,
+        };
+        previous = Some(op);
+    }
+    Some(score)
+}
+
+/// Iterates over the columns of an alignment as (query byte, target byte) pairs, starting at startLocations\[0\]
+/// in target : (Some(q), Some(t)) for a match or mismatch, (Some(q), None) for an insertion to target
+/// and (None, Some(t)) for an insertion to query (deletion).
+/// This is the primitive to render or score an alignment column by column with the actual residues.
+///
+/// Returns None if result has no alignment path or start location, if the start location is negative,
+/// if the path contains an invalid opcode or if it does not fit query and target (the sequences are not
+/// the ones aligned).
+pub fn aligned_columns<'a>(
+    query: &'a [u8],
+    target: &'a [u8],
+    result: &'a EdlibAlignResultRs,
+) -> Option<impl Iterator<Item = (Option<u8>, Option<u8>)> + 'a> {
+    let alignment = result.alignment()?;
+    let target_start = *result.start_locations()?.first()?;
+    if target_start < 0 {
+        return None;
+    }
+    let target_start = target_start as usize;
+    // check the whole path fits before iterating, so the iterator never fails
+    let mut query_len = 0;
+    let mut target_len = 0;
+    for &op in alignment {
+        match op {
+            0 | 3 => {
+                query_len += 1;
+                target_len += 1;
+            }
+            1 => query_len += 1,
+            2 => target_len += 1,
+            _ => return None,
+        }
+    }
+    if query_len > query.len() || target_start + target_len > target.len() {
+        return None;
+    }
+    let mut query_pos = 0;
+    let mut target_pos = target_start;
+    Some(alignment.iter().map(move |&op| {
+        let q = if op != EdlibEdopRs::EDLIB_EDOP_DELETE as u8 {
+            query_pos += 1;
+            Some(query[query_pos - 1])
+        } else {
+            None
+        };
+        let t = if op != EdlibEdopRs::EDLIB_EDOP_INSERT as u8 {
+            target_pos += 1;
+            Some(target[target_pos - 1])
+        } else {
+            None
+        };
+        (q, t)
+    }))
+} // end of aligned_columns
+
+/// An edit of an edit script, see edit_script. Positions are 0-based, ranges are exclusive.
+/// As for alignment opcodes, the script edits target into query : Insert adds query residues to target
+/// and Delete removes target residues.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditOp {
+    /// len residues identical in query and target
+    Equal { len: usize },
+    /// target residues in target_range replaced by the query residues in query_range, of same length
+    Replace {
+        query_range: std::ops::Range<usize>,
+        target_range: std::ops::Range<usize>,
+    },
+    /// query residues text inserted in target before target position at
+    Insert { at: usize, text: Vec<u8> },
+    /// target residues in range deleted
+    Delete { range: std::ops::Range<usize> },
+}
+
+/// Builds the edit script of an alignment : runs of identical opcodes of the path are coalesced into one EditOp,
+/// target positions starting at startLocations\[0\].
+///
+/// Returns None if result has no alignment path or start location, if the start location is negative,
+/// if the path contains an invalid opcode or if it does not fit query and target.
+pub fn edit_script(
+    query: &[u8],
+    target: &[u8],
+    result: &EdlibAlignResultRs,
+) -> Option<Vec<EditOp>> {
+    let alignment = result.alignment()?;
+    let target_start = *result.start_locations()?.first()?;
+    if target_start < 0 {
+        return None;
+    }
+    let mut target_pos = target_start as usize;
+    let mut query_pos = 0usize;
+    let mut script = Vec::<EditOp>::new();
+    let mut i = 0;
+    while i < alignment.len() {
+        let op = alignment[i];
+        let run = alignment[i..].iter().take_while(|&&o| o == op).count();
+        let (query_run, target_run) = match op {
+            0 | 3 => (run, run),
+            1 => (run, 0),
+            2 => (0, run),
+            _ => return None,
+        };
+        let query_range = query_pos..query_pos + query_run;
+        let target_range = target_pos..target_pos + target_run;
+        if query_range.end > query.len() || target_range.end > target.len() {
+            return None;
+        }
+        script.push(match op {
+            0 => EditOp::Equal { len: run },
+            3 => EditOp::Replace {
+                query_range,
+                target_range,
+            },
+            1 => EditOp::Insert {
+                at: target_pos,
+                text: query[query_range].to_vec(),
+            },
+            _ => EditOp::Delete {
+                range: target_range,
+            },
+        });
+        query_pos += query_run;
+        target_pos += target_run;
+        i += run;
+    }
+    Some(script)
+} // end of edit_script
+
+/// Error returned by align_and_verify when an invariant of the result does not hold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyError {
+    /// edlib returned status EDLIB_STATUS_ERROR.
+    Status,
+    /// the alignment path does not align query to target, see validate_alignment_with_equalities.
+    Alignment(AlignmentMismatch),
+    /// the path could not be converted to a cigar by edlib.
+    Cigar(CigarError),
+    /// the cigar produced by edlib could not be parsed.
+    CigarParse(CigarParseError),
+    /// the cigar does not consume the aligned target span and the whole query.
+    /// Holds the (reference, query) lengths consumed by the cigar.
+    CigarLengths { reference: usize, query: usize },
+    /// the number of edit opcodes of the path differs from editDistance.
+    Distance { path_edits: usize, distance: i32 },
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VerifyError::Status => write!(f, "edlib returned an error status"),
+            VerifyError::Alignment(e) => write!(f, "invalid alignment: {}", e),
+            VerifyError::Cigar(e) => write!(f, "cigar conversion failed: {}", e),
+            VerifyError::CigarParse(e) => write!(f, "invalid cigar: {}", e),
+            VerifyError::CigarLengths { reference, query } => write!(
+                f,
+                "cigar consumes {} reference and {} query residues, not matching the alignment",
+                reference, query
+            ),
+            VerifyError::Distance {
+                path_edits,
+                distance,
+            } => write!(
+                f,
+                "path has {} edits but edit distance is {}",
+                path_edits, distance
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+impl From<AlignmentMismatch> for VerifyError {
+    fn from(e: AlignmentMismatch) -> Self {
+        VerifyError::Alignment(e)
+    }
+}
+
+impl From<CigarError> for VerifyError {
+    fn from(e: CigarError) -> Self {
+        VerifyError::Cigar(e)
+    }
+}
+
+impl From<CigarParseError> for VerifyError {
+    fn from(e: CigarParseError) -> Self {
+        VerifyError::CigarParse(e)
+    }
+}
+
+/// Aligns query against target with config and, when an alignment path was produced, checks the invariants
+/// relating the result to the sequences : the path aligns query to target (validate_alignment_with_equalities
+/// with the equalities of config), its number of edits is editDistance, and the cigar computed by edlib
+/// consumes the whole query and the target span startLocations\[0\]..=endLocations\[0\].
+/// A violated invariant reveals a bug in edlib or in the bindings (coordinates, copies of C arrays...).
+///
+/// It does not panic on any input, so it can be used directly as a fuzz target, for example with cargo fuzz :
+/// ```ignore
+/// fuzz_target!(|data: &[u8]| {
+///     let (query, target) = data.split_at(data.len() / 2);
+///     let mut config = edlib_rs::EdlibAlignConfigRs::infix();
+///     config.task = edlib_rs::EdlibAlignTaskRs::EDLIB_TASK_PATH;
+///     edlib_rs::align_and_verify(query, target, &config).unwrap();
+/// });
+/// ```
+pub fn align_and_verify(
+    query: &[u8],
+    target: &[u8],
+    config: &EdlibAlignConfigRs,
+) -> Result<EdlibAlignResultRs, VerifyError> {
+    let result = edlibAlignRs(query, target, config);
+    if result.status != EDLIB_STATUS_OK {
+        return Err(VerifyError::Status);
+    }
+    let alignment = match result.alignment() {
+        Some(alignment) => alignment,
+        None => return Ok(result),
+    };
+    let equalities = Equalities::new(config.additionalequalities);
+    validate_alignment_with_equalities(query, target, &result, &equalities)?;
+    let path_edits = alignment
+        .iter()
+        .filter(|&&op| op != EdlibEdopRs::EDLIB_EDOP_MATCH as u8)
+        .count();
+    if result.editDistance < 0 || path_edits != result.editDistance as usize {
+        return Err(VerifyError::Distance {
+            path_edits,
+            distance: result.editDistance,
+        });
+    }
+    let cigar = edlibTryAlignmentToCigarRs(alignment, &EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED)?;
+    let (reference, query_consumed) = cigar_consumed_lengths(&cigar)?;
+    // validate_alignment checked locations are present and consistent with the path
+    let target_span = match (result.start_locations(), result.end_locations()) {
+        (Some(starts), Some(ends)) => (ends[0] + 1 - starts[0]) as usize,
+        _ => return Err(VerifyError::Alignment(AlignmentMismatch::NoAlignment)),
+    };
+    if reference != target_span || query_consumed != query.len() {
+        return Err(VerifyError::CigarLengths {
+            reference,
+            query: query_consumed,
+        });
+    }
+    Ok(result)
+} // end of align_and_verify
+
+//===================================================================
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_trim_flanking_gaps() {
+        let query = "GGACGTAATT";
+        let target = "ACGTCAATTT";
+        //
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let alignment = align_res.alignment().unwrap();
+        // 2 leading query only residues, internal gap kept
+        assert_eq!(alignment[..2], [1, 1]);
+        let (trimmed, leading, trailing) = trim_flanking_gaps(alignment);
+        assert_eq!((leading, trailing), (2, 0));
+        assert_eq!(trimmed, &alignment[2..]);
+        assert!(trimmed.contains(&2));
+        // constructed path
+        let (trimmed, leading, trailing) = trim_flanking_gaps(&[2, 1, 0, 2, 3, 1]);
+        assert_eq!((trimmed, leading, trailing), (&[0, 2, 3][..], 2, 1));
+        let (trimmed, leading, trailing) = trim_flanking_gaps(&[1, 1]);
+        assert_eq!((trimmed.len(), leading, trailing), (0, 2, 0));
+    } // end of test_trim_flanking_gaps
+
+    #[test]
+    fn test_reverse_alignment() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(b"AACCC", b"AAGCCCT", &config);
+        let alignment = align_res.alignment().unwrap();
+        let reversed = reverse_alignment(alignment);
+        assert_eq!(&reverse_alignment(&reversed), alignment);
+        let format = EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED;
+        assert_eq!(edlibAlignmentToCigarRs(alignment, &format), "2=1D3=1D");
+        // compare with a fresh alignment of swapped and reversed sequences
+        let align_res = edlibAlignRs(b"TCCCGAA", b"CCCAA", &config);
+        let fresh = align_res.alignment().unwrap();
+        assert_eq!(
+            edlibAlignmentToCigarRs(&reversed, &format),
+            edlibAlignmentToCigarRs(fresh, &format)
+        );
+    } // end of test_reverse_alignment
+
+    #[test]
+    fn test_validate_alignment() {
+        let query = "missing";
+        let target = "mississipi";
+        //
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(
+            validate_alignment(query.as_bytes(), target.as_bytes(), &align_res),
+            Ok(())
+        );
+        // wrong sequences
+        assert_eq!(
+            validate_alignment(b"mostly", target.as_bytes(), &align_res),
+            Err(AlignmentMismatch::NotEqual {
+                column: 1,
+                query_pos: 1,
+                target_pos: 1
+            })
+        );
+        assert_eq!(
+            validate_alignment(b"missingg", target.as_bytes(), &align_res),
+            Err(AlignmentMismatch::LengthMismatch {
+                query_consumed: 7,
+                target_consumed: 5
+            })
+        );
+        // corrupted path
+        let mut corrupted = align_res.clone();
+        corrupted.alignment.as_mut().unwrap()[2] = 3;
+        assert_eq!(
+            validate_alignment(query.as_bytes(), target.as_bytes(), &corrupted),
+            Err(AlignmentMismatch::NotDifferent {
+                column: 2,
+                query_pos: 2,
+                target_pos: 2
+            })
+        );
+        corrupted.alignment.as_mut().unwrap()[2] = 2;
+        assert!(validate_alignment(query.as_bytes(), target.as_bytes(), &corrupted).is_err());
+        // no path
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(
+            validate_alignment(query.as_bytes(), target.as_bytes(), &align_res),
+            Err(AlignmentMismatch::NoAlignment)
+        );
+    } // end of test_validate_alignment
+
+    #[test]
+    fn test_target_coverage() {
+        let target = "TTACGTACGGAA";
+        //
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        // aligned on 2..=5 and 4..=9 with an insertion to target
+        let res1 = edlibAlignRs(b"ACGT", target.as_bytes(), &config);
+        let res2 = edlibAlignRs(b"GTACCGG", target.as_bytes(), &config);
+        assert_eq!(res1.start_locations().unwrap()[0], 2);
+        assert_eq!(res2.start_locations().unwrap()[0], 4);
+        let results = vec![
+            (res1.start_locations().unwrap()[0], &res1),
+            (res2.start_locations().unwrap()[0], &res2),
+        ];
+        let coverage = target_coverage(target.len(), &results);
+        assert_eq!(coverage, vec![0, 0, 1, 1, 2, 2, 1, 1, 1, 1, 0, 0]);
+    } // end of test_target_coverage
+
+    #[test]
+    fn test_weighted_score() {
+        let query = "missing";
+        let target = "mississipi";
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        // 5M2I
+        let alignment = align_res.alignment().unwrap();
+        assert_eq!(weighted_score(alignment, 1, -1, -1), Some(3));
+        assert_eq!(weighted_score(&[0, 3, 3, 2, 0], 2, -3, -5), Some(-7));
+        assert_eq!(weighted_score(&[], 1, -1, -1), None);
+        assert_eq!(weighted_score(&[0, 4], 1, -1, -1), None);
+    } // end of test_weighted_score
+
+    #[test]
+    fn test_matches_via_equalities() {
+        let query = "ACCTCTG";
+        let target = "TTTTTTTTTTNCCTCTGTTT";
+        let equalities = [EdlibEqualityPairRs::new(b'A', b'N')];
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        config.additionalequalities = &equalities;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert_eq!(align_res.editDistance, 0);
+        let columns =
+            matches_via_equalities(query.as_bytes(), target.as_bytes(), &align_res, &equalities);
+        assert_eq!(columns, Some(vec![0]));
+        // no equality given, nothing is reported
+        let columns = matches_via_equalities(query.as_bytes(), target.as_bytes(), &align_res, &[]);
+        assert_eq!(columns, Some(vec![]));
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        assert!(matches_via_equalities(
+            query.as_bytes(),
+            target.as_bytes(),
+            &align_res,
+            &equalities
+        )
+        .is_none());
+    } // end of test_matches_via_equalities
+
+    #[test]
+    fn test_validate_with_equalities() {
+        let query = "ACCTCTG";
+        let target = "TTTTTTTTTTNCCTCTGTTT";
+        let pairs = [EdlibEqualityPairRs::new(b'A', b'N')];
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        config.additionalequalities = &pairs;
+        let align_res = edlibAlignRs(query.as_bytes(), target.as_bytes(), &config);
+        let equalities = Equalities::new(&pairs);
+        assert!(validate_alignment_with_equalities(
+            query.as_bytes(),
+            target.as_bytes(),
+            &align_res,
+            &equalities
+        )
+        .is_ok());
+        // A matched to N is rejected with strict comparison
+        assert!(matches!(
+            validate_alignment(query.as_bytes(), target.as_bytes(), &align_res),
+            Err(AlignmentMismatch::NotEqual { column: 0, .. })
+        ));
+    } // end of test_validate_with_equalities
+
+    #[test]
+    fn test_affine_score() {
+        // 2 matches, a gap of 3 insertions, 1 mismatch, a gap of 1 insertion then a gap of 2 deletions, 1 match
+        let alignment = [0, 0, 1, 1, 1, 3, 1, 2, 2, 0];
+        // matches 3 * 2 = 6, mismatch -4, gap opens 3 * -5 = -15, extends 6 * -1 = -6
+        assert_eq!(affine_score(&alignment, 2, -4, -5, -1), Some(-19));
+        // with gap_open 0 this is weighted_score
+        assert_eq!(
+            affine_score(&alignment, 2, -4, 0, -1),
+            weighted_score(&alignment, 2, -4, -1)
+        );
+        assert_eq!(affine_score(&[], 1, -1, -2, -1), None);
+        assert_eq!(affine_score(&[0, 5], 1, -1, -2, -1), None);
+    } // end of test_affine_score
+
+    #[test]
+    fn test_aligned_columns() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let query = b"missing";
+        let target = b"mississipi";
+        let align_res = edlibAlignRs(query, target, &config);
+        // 5M2I from target position 0
+        let columns: Vec<(Option<u8>, Option<u8>)> = aligned_columns(query, target, &align_res)
+            .unwrap()
+            .collect();
+        let expected: Vec<(Option<u8>, Option<u8>)> = vec![
+            (Some(b'm'), Some(b'm')),
+            (Some(b'i'), Some(b'i')),
+            (Some(b's'), Some(b's')),
+            (Some(b's'), Some(b's')),
+            (Some(b'i'), Some(b'i')),
+            (Some(b'n'), None),
+            (Some(b'g'), None),
+        ];
+        assert_eq!(columns, expected);
+        // deletion and mismatch with NW
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_NW;
+        let align_res = edlibAlignRs(b"ACT", b"AGCA", &config);
+        let columns: Vec<(Option<u8>, Option<u8>)> = aligned_columns(b"ACT", b"AGCA", &align_res)
+            .unwrap()
+            .collect();
+        assert_eq!(
+            columns,
+            vec![
+                (Some(b'A'), Some(b'A')),
+                (None, Some(b'G')),
+                (Some(b'C'), Some(b'C')),
+                (Some(b'T'), Some(b'A')),
+            ]
+        );
+        // sequences not matching the path
+        assert!(aligned_columns(b"AC", b"AGCA", &align_res).is_none());
+        // negative start in a hand built result
+        let mut bad_start = align_res.clone();
+        bad_start.startLocations = Some(vec![-1]);
+        assert!(aligned_columns(query, target, &bad_start).is_none());
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(query, target, &config);
+        assert!(aligned_columns(query, target, &align_res).is_none());
+    } // end of test_aligned_columns
+
+    #[test]
+    fn test_edit_script() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let align_res = edlibAlignRs(b"kitten", b"sitting", &config);
+        assert_eq!(align_res.editDistance, 3);
+        let script = edit_script(b"kitten", b"sitting", &align_res).unwrap();
+        assert_eq!(
+            script,
+            vec![
+                EditOp::Replace {
+                    query_range: 0..1,
+                    target_range: 0..1
+                },
+                EditOp::Equal { len: 3 },
+                EditOp::Replace {
+                    query_range: 4..5,
+                    target_range: 4..5
+                },
+                EditOp::Equal { len: 1 },
+                EditOp::Delete { range: 6..7 },
+            ]
+        );
+        // insertion of query residues
+        let align_res = edlibAlignRs(b"ACGGGT", b"ACT", &config);
+        let script = edit_script(b"ACGGGT", b"ACT", &align_res).unwrap();
+        assert_eq!(
+            script,
+            vec![
+                EditOp::Equal { len: 2 },
+                EditOp::Insert {
+                    at: 2,
+                    text: b"GGG".to_vec()
+                },
+                EditOp::Equal { len: 1 },
+            ]
+        );
+        // negative start in a hand built result
+        let mut bad_start = align_res.clone();
+        bad_start.startLocations = Some(vec![-1]);
+        assert!(edit_script(b"kitten", b"sitting", &bad_start).is_none());
+        // no path
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(b"kitten", b"sitting", &config);
+        assert!(edit_script(b"kitten", b"sitting", &align_res).is_none());
+    } // end of test_edit_script
+
+    #[test]
+    fn test_align_and_verify() {
+        // small pseudo random sequences over a small alphabet, so that alignments have all kinds of edits
+        let mut state: u32 = 12345;
+        let mut next = |bound: u32| {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (state >> 16) % bound
+        };
+        let pairs = [EdlibEqualityPairRs::new(b'A', b'N')];
+        let modes = [
+            EdlibAlignModeRs::EDLIB_MODE_NW,
+            EdlibAlignModeRs::EDLIB_MODE_SHW,
+            EdlibAlignModeRs::EDLIB_MODE_HW,
+        ];
+        for i in 0..300 {
+            let query: Vec<u8> = (0..next(12)).map(|_| b"ACGTN"[next(5) as usize]).collect();
+            let target: Vec<u8> = (0..next(20)).map(|_| b"ACGTN"[next(5) as usize]).collect();
+            let mut config = EdlibAlignConfigRs::default();
+            config.mode = modes[i % 3];
+            config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+            if i % 2 == 0 {
+                config.additionalequalities = &pairs;
+            }
+            if i % 5 == 0 {
+                config.k = 2;
+            }
+            let res = align_and_verify(&query, &target, &config);
+            assert!(res.is_ok(), "{:?} {:?} {:?}", query, target, res);
+        }
+    } // end of test_align_and_verify
+} // mod tests
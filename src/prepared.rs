@@ -0,0 +1,102 @@
+//! Alignment of one query against many targets with PreparedQuery.
+
+use crate::*;
+
+/// A query with its configuration, checked once, to align against many targets.
+/// This is the "one query, many targets" pattern done one target at a time, by the caller's loop,
+/// whereas batch functions take all targets at once.
+#[derive(Debug, Clone, Copy)]
+pub struct PreparedQuery<'a> {
+    query: &'a [u8],
+    config: EdlibAlignConfigRs<'a>,
+}
+
+impl<'a> PreparedQuery<'a> {
+    /// Checks query and config once for all alignments.
+    ///
+    /// **Panics** if query is longer than i32::MAX, the largest length edlib accepts,
+    /// or if config.k is less than -1 (k = -1 means no limit).
+    pub fn new(query: &'a [u8], config: &EdlibAlignConfigRs<'a>) -> Self {
+        assert!(
+            query.len() <= i32::MAX as usize,
+            "query length {} exceeds edlib limit {}",
+            query.len(),
+            i32::MAX
+        );
+        assert!(config.k >= -1, "invalid k {}", config.k);
+        PreparedQuery {
+            query,
+            config: *config,
+        }
+    }
+
+    /// the query given to new
+    pub fn query(&self) -> &'a [u8] {
+        self.query
+    }
+
+    /// the configuration given to new
+    pub fn config(&self) -> &EdlibAlignConfigRs<'a> {
+        &self.config
+    }
+
+    /// Aligns the query against target, as edlibAlignRs(query, target, config) would.
+    /// If target is longer than i32::MAX the result has status EDLIB_STATUS_ERROR, edlib is not called.
+    pub fn align(&self, target: &[u8]) -> EdlibAlignResultRs {
+        let mut align_res = EdlibAlignResultRs::default();
+        self.align_into(target, &mut align_res);
+        align_res
+    }
+
+    /// Same as align but reuses the allocations of out, see edlibAlignInto.
+    pub fn align_into(&self, target: &[u8], out: &mut EdlibAlignResultRs) {
+        if target.len() > i32::MAX as usize {
+            *out = EdlibAlignResultRs {
+                status: EDLIB_STATUS_ERROR,
+                editDistance: -1,
+                ..Default::default()
+            };
+            return;
+        }
+        edlibAlignInto(self.query, target, &self.config, out);
+    }
+} // end impl PreparedQuery
+
+//===================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prepared_query() {
+        let query = b"ACCTCTG";
+        let targets: [&[u8]; 4] = [
+            b"TTTTTTTTTTTTTTTTTTTTTACTCTGAAA",
+            b"ACCTCTG",
+            b"GGGGGGGG",
+            b"",
+        ];
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let prepared = PreparedQuery::new(query, &config);
+        for target in targets.iter() {
+            let align_res = prepared.align(target);
+            let expected = edlibAlignRs(query, target, &config);
+            assert_eq!(align_res.status, expected.status);
+            assert_eq!(align_res.editDistance, expected.editDistance);
+            assert_eq!(align_res.end_locations(), expected.end_locations());
+            assert_eq!(align_res.start_locations(), expected.start_locations());
+            assert_eq!(align_res.alignment(), expected.alignment());
+        }
+    } // end of test_prepared_query
+
+    #[test]
+    #[should_panic]
+    fn test_prepared_query_invalid_k() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.k = -2;
+        PreparedQuery::new(b"ACGT", &config);
+    } // end of test_prepared_query_invalid_k
+} // mod tests
@@ -0,0 +1,171 @@
+//! Zero-copy access to edlib results with edlibAlignBorrowed, for read-only consumers who do not need
+//! the owned vectors of EdlibAlignResultRs.
+
+use crate::*;
+
+/// Raw result of edlib, owning the C allocations of locations and alignment path, freed on drop.
+/// Accessors return slices borrowing the C arrays, without copy.
+///
+/// The content is what edlib returns : contrary to edlibAlignRs, results of empty sequences are not completed
+/// (no start location nor path is given by edlib when query or target is empty).
+pub struct OwnedRawResult {
+    raw: EdlibAlignResult,
+}
+
+// The C arrays are owned by OwnedRawResult only and are never mutated, they can be read from and freed in any thread.
+unsafe impl Send for OwnedRawResult {}
+unsafe impl Sync for OwnedRawResult {}
+
+impl OwnedRawResult {
+    /// see EdlibAlignResultRs::status
+    pub fn status(&self) -> u32 {
+        self.raw.status as u32
+    }
+
+    /// see EdlibAlignResultRs::edit_distance
+    pub fn edit_distance(&self) -> i32 {
+        self.raw.editDistance
+    }
+
+    /// see EdlibAlignResultRs::alphabet_length
+    pub fn alphabet_length(&self) -> u32 {
+        self.raw.alphabetLength as u32
+    }
+
+    /// see EdlibAlignResultRs::num_locations
+    pub fn num_locations(&self) -> usize {
+        self.end_locations().len()
+    }
+
+    /// end locations of optimal alignment paths (0-based), empty if there is none.
+    pub fn end_locations(&self) -> &[i32] {
+        if self.raw.numLocations <= 0 || self.raw.endLocations.is_null() {
+            return &[];
+        }
+        unsafe { slice::from_raw_parts(self.raw.endLocations, self.raw.numLocations as usize) }
+    }
+
+    /// start locations of optimal alignment paths (0-based), None if not computed (task EDLIB_TASK_DISTANCE).
+    pub fn start_locations(&self) -> Option<&[i32]> {
+        if self.raw.numLocations <= 0 || self.raw.startLocations.is_null() {
+            return None;
+        }
+        unsafe {
+            Some(slice::from_raw_parts(
+                self.raw.startLocations,
+                self.raw.numLocations as usize,
+            ))
+        }
+    }
+
+    /// alignment path of the first location, None if not computed (task other than EDLIB_TASK_PATH).
+    pub fn alignment(&self) -> Option<&[u8]> {
+        if self.raw.alignmentLength <= 0 || self.raw.alignment.is_null() {
+            return None;
+        }
+        unsafe {
+            Some(slice::from_raw_parts(
+                self.raw.alignment,
+                self.raw.alignmentLength as usize,
+            ))
+        }
+    }
+
+    /// builds the cigar of the alignment path with alignment_to_cigar_native, straight from the C array,
+    /// giving the same output as edlibAlignmentToCigarRs. None if there is no alignment path.
+    pub fn to_cigar(&self, format: EdlibCigarFormatRs) -> Option<String> {
+        Some(alignment_to_cigar_native(self.alignment()?, format))
+    }
+
+    /// copies the raw result into an EdlibAlignResultRs, see EdlibAlignResultRs::from_raw.
+    pub fn to_result(&self) -> EdlibAlignResultRs {
+        unsafe { EdlibAlignResultRs::from_raw(&self.raw) }
+    }
+} // end impl OwnedRawResult
+
+impl Drop for OwnedRawResult {
+    fn drop(&mut self) {
+        unsafe {
+            edlibFreeAlignResult(self.raw);
+        }
+    }
+}
+
+/// Same as edlibAlignRs but returns the result of edlib as is, without copying locations and alignment path
+/// in vectors : they are borrowed from the C allocations, which are freed when the OwnedRawResult is dropped.
+#[allow(non_snake_case)]
+pub fn edlibAlignBorrowed(
+    query: &[u8],
+    target: &[u8],
+    config: &EdlibAlignConfigRs,
+) -> OwnedRawResult {
+    OwnedRawResult {
+        raw: alignC(query, target, config),
+    }
+}
+
+//===================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_align_borrowed() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        let cases: [(&[u8], &[u8]); 3] = [
+            (b"missing", b"mississipi"),
+            (b"ACCTCTG", b"TTTTTTTTTTTTTTTTTTTTTACTCTGAAA"),
+            (b"AC", b"ACACAC"),
+        ];
+        for task in [
+            EdlibAlignTaskRs::EDLIB_TASK_DISTANCE,
+            EdlibAlignTaskRs::EDLIB_TASK_LOC,
+            EdlibAlignTaskRs::EDLIB_TASK_PATH,
+        ]
+        .iter()
+        {
+            config.task = *task;
+            for &(query, target) in cases.iter() {
+                let raw = edlibAlignBorrowed(query, target, &config);
+                let expected = edlibAlignRs(query, target, &config);
+                assert_eq!(raw.status(), expected.status);
+                assert_eq!(raw.edit_distance(), expected.editDistance);
+                assert_eq!(raw.alphabet_length(), expected.alphabetLength);
+                assert_eq!(raw.num_locations(), expected.numLocations);
+                assert_eq!(Some(raw.end_locations()), expected.end_locations());
+                assert_eq!(raw.start_locations(), expected.start_locations());
+                assert_eq!(raw.alignment(), expected.alignment());
+                assert_eq!(raw.to_result().alignment(), expected.alignment());
+            }
+        }
+    } // end of test_align_borrowed
+
+    #[test]
+    fn test_raw_to_cigar() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let raw = edlibAlignBorrowed(b"missing", b"mississipi", &config);
+        let align_res = edlibAlignRs(b"missing", b"mississipi", &config);
+        for format in [
+            EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD,
+            EdlibCigarFormatRs::EDLIB_CIGAR_EXTENDED,
+        ]
+        .iter()
+        {
+            let expected = edlibAlignmentToCigarRs(align_res.alignment().unwrap(), format);
+            assert_eq!(raw.to_cigar(*format), Some(expected));
+        }
+        assert_eq!(
+            raw.to_cigar(EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD),
+            Some(String::from("5M2I"))
+        );
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let raw = edlibAlignBorrowed(b"missing", b"mississipi", &config);
+        assert!(raw
+            .to_cigar(EdlibCigarFormatRs::EDLIB_CIGAR_STANDARD)
+            .is_none());
+    } // end of test_raw_to_cigar
+} // mod tests
@@ -0,0 +1,100 @@
+//! Alignment against several reference sequences packed in one target.
+//! References are concatenated with a separator byte, query is aligned (in HW mode typically) against the
+//! concatenation and locations are mapped back to a reference and a position in it with RefIndex.
+
+/// Index of the references packed in a concatenated target by RefIndex::from_sequences.
+#[derive(Debug, Clone)]
+pub struct RefIndex {
+    // start of each reference in the concatenated target
+    offsets: Vec<usize>,
+    names: Vec<String>,
+    lengths: Vec<usize>,
+}
+
+impl RefIndex {
+    /// Concatenates the (name, sequence) references, with the byte sep between two consecutive references,
+    /// and returns the concatenated target with its index.
+    /// sep should be a byte present in no reference (and no query), so that no alignment match it,
+    /// though an alignment can still span it with a mismatch or a deletion.
+    pub fn from_sequences(seqs: &[(&str, &[u8])], sep: u8) -> (Vec<u8>, RefIndex) {
+        let total: usize = seqs.iter().map(|(_, seq)| seq.len() + 1).sum();
+        let mut target = Vec::<u8>::with_capacity(total);
+        let mut index = RefIndex {
+            offsets: Vec::with_capacity(seqs.len()),
+            names: Vec::with_capacity(seqs.len()),
+            lengths: Vec::with_capacity(seqs.len()),
+        };
+        for (i, (name, seq)) in seqs.iter().enumerate() {
+            if i > 0 {
+                target.push(sep);
+            }
+            index.offsets.push(target.len());
+            index.names.push(name.to_string());
+            index.lengths.push(seq.len());
+            target.extend_from_slice(seq);
+        }
+        (target, index)
+    }
+
+    /// Maps a location in the concatenated target, as found in startLocations or endLocations,
+    /// to the name of its reference and the 0-based position in this reference.
+    /// Returns None for a negative location, a separator position or a location past the end.
+    pub fn resolve(&self, global_pos: i32) -> Option<(&str, usize)> {
+        if global_pos < 0 {
+            return None;
+        }
+        let pos = global_pos as usize;
+        // last reference starting at or before pos
+        let i = self
+            .offsets
+            .partition_point(|&offset| offset <= pos)
+            .checked_sub(1)?;
+        let local = pos - self.offsets[i];
+        if local >= self.lengths[i] {
+            return None;
+        }
+        Some((&self.names[i], local))
+    }
+
+    /// get the number of references
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// true if there is no reference
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+} // end impl RefIndex
+
+//===================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn test_ref_index() {
+        let refs: Vec<(&str, &[u8])> = vec![("chr1", b"TTTTGGGGCCCC"), ("chr2", b"AAAACGTACGTTTT")];
+        let (target, index) = RefIndex::from_sequences(&refs, b'$');
+        assert_eq!(target, b"TTTTGGGGCCCC$AAAACGTACGTTTT".to_vec());
+        assert_eq!(index.len(), 2);
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_LOC;
+        let align_res = edlibAlignRs(b"CGTACG", &target, &config);
+        assert_eq!(align_res.editDistance, 0);
+        let start = align_res.start_locations().unwrap()[0];
+        let end = align_res.end_locations().unwrap()[0];
+        assert_eq!(index.resolve(start), Some(("chr2", 4)));
+        assert_eq!(index.resolve(end), Some(("chr2", 9)));
+        //
+        assert_eq!(index.resolve(0), Some(("chr1", 0)));
+        assert_eq!(index.resolve(11), Some(("chr1", 11)));
+        assert_eq!(index.resolve(12), None);
+        assert_eq!(index.resolve(13), Some(("chr2", 0)));
+        assert_eq!(index.resolve(27), None);
+        assert_eq!(index.resolve(-1), None);
+    } // end of test_ref_index
+} // mod tests
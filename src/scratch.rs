@@ -0,0 +1,188 @@
+//! Alignment into reusable buffers with AlignScratch, for callers running many alignments who want
+//! to avoid allocating result vectors for each of them.
+
+use crate::*;
+
+/// Buffers reused by align_with_scratch for end locations, start locations and alignment path.
+/// They grow to the largest result seen and are never shrunk, so after a few alignments no more
+/// allocation is done.
+#[derive(Debug, Clone, Default)]
+pub struct AlignScratch {
+    ends: Vec<i32>,
+    starts: Vec<i32>,
+    path: Vec<u8>,
+}
+
+impl AlignScratch {
+    /// returns empty buffers, allocated at first use.
+    pub fn new() -> Self {
+        AlignScratch::default()
+    }
+}
+
+/// Result of align_with_scratch, borrowing the AlignScratch buffers.
+/// Accessors are the same as EdlibAlignResultRs ones, use to_result to get an owned copy.
+#[derive(Debug, Clone, Copy)]
+pub struct EdlibAlignView<'a> {
+    status: u32,
+    edit_distance: i32,
+    alphabet_length: u32,
+    ends: &'a [i32],
+    starts: Option<&'a [i32]>,
+    path: &'a [u8],
+}
+
+impl<'a> EdlibAlignView<'a> {
+    /// see EdlibAlignResultRs::status
+    pub fn status(&self) -> u32 {
+        self.status
+    }
+    /// see EdlibAlignResultRs::edit_distance
+    pub fn edit_distance(&self) -> i32 {
+        self.edit_distance
+    }
+    /// see EdlibAlignResultRs::alphabet_length
+    pub fn alphabet_length(&self) -> u32 {
+        self.alphabet_length
+    }
+    /// see EdlibAlignResultRs::num_locations
+    pub fn num_locations(&self) -> usize {
+        self.ends.len()
+    }
+    /// see EdlibAlignResultRs::end_locations
+    pub fn end_locations(&self) -> Option<&'a [i32]> {
+        if self.ends.is_empty() {
+            None
+        } else {
+            Some(self.ends)
+        }
+    }
+    /// see EdlibAlignResultRs::start_locations
+    pub fn start_locations(&self) -> Option<&'a [i32]> {
+        self.starts
+    }
+    /// see EdlibAlignResultRs::alignment
+    pub fn alignment(&self) -> Option<&'a [u8]> {
+        if self.path.is_empty() {
+            None
+        } else {
+            Some(self.path)
+        }
+    }
+    /// copies the view in an owned EdlibAlignResultRs, as returned by edlibAlignRs.
+    pub fn to_result(&self) -> EdlibAlignResultRs {
+        EdlibAlignResultRs {
+            status: self.status,
+            editDistance: self.edit_distance,
+            endLocations: self.end_locations().map(|s| s.to_vec()),
+            startLocations: self.start_locations().map(|s| s.to_vec()),
+            numLocations: self.num_locations(),
+            alignment: self.alignment().map(|s| s.to_vec()),
+            alphabetLength: self.alphabet_length,
+        }
+    }
+} // end impl EdlibAlignView
+
+/// Same as edlibAlignRs but the locations and alignment path returned by edlib are copied into the buffers
+/// of scratch, and the result borrows them. Contrary to edlibAlignInto, buffers are kept when a result
+/// has no locations or path, so one scratch can serve alignments with different tasks without reallocating.
+pub fn align_with_scratch<'s>(
+    query: &[u8],
+    target: &[u8],
+    config: &EdlibAlignConfigRs,
+    scratch: &'s mut AlignScratch,
+) -> EdlibAlignView<'s> {
+    scratch.ends.clear();
+    scratch.starts.clear();
+    scratch.path.clear();
+    let res_c = alignC(query, target, config);
+    let (status, mut has_starts) = unsafe {
+        let slices = slicesFromC(&res_c);
+        if let Some(ends) = slices.ends {
+            scratch.ends.extend_from_slice(ends);
+        }
+        if let Some(starts) = slices.starts {
+            scratch.starts.extend_from_slice(starts);
+        }
+        if let Some(alignment) = slices.alignment {
+            scratch.path.extend_from_slice(alignment);
+        }
+        (slices.status, slices.starts.is_some())
+    };
+    let edit_distance = res_c.editDistance;
+    let alphabet_length = res_c.alphabetLength as u32;
+    unsafe {
+        edlibFreeAlignResult(res_c);
+    }
+    if let Some(empty) = EmptySequencePath::new(query, target, config, status, &scratch.ends) {
+        scratch.starts.clear();
+        scratch.starts.push(0);
+        has_starts = true;
+        if empty.with_path {
+            empty.write_path(&mut scratch.path);
+        }
+    }
+    EdlibAlignView {
+        status,
+        edit_distance,
+        alphabet_length,
+        ends: &scratch.ends,
+        starts: if has_starts {
+            Some(&scratch.starts)
+        } else {
+            None
+        },
+        path: &scratch.path,
+    }
+} // end of align_with_scratch
+
+//===================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_align_with_scratch() {
+        let mut config = EdlibAlignConfigRs::default();
+        config.mode = EdlibAlignModeRs::EDLIB_MODE_HW;
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_PATH;
+        let pairs: [(&[u8], &[u8]); 4] = [
+            (b"missing", b"mississipi"),
+            (b"ACCTCTG", b"TTTTTTTTTTTTTTTTTTTTTACTCTGAAA"),
+            (b"ACGT", b""),
+            (b"AC", b"ACAC"),
+        ];
+        let mut scratch = AlignScratch::new();
+        for &(query, target) in pairs.iter() {
+            let view = align_with_scratch(query, target, &config, &mut scratch);
+            let expected = edlibAlignRs(query, target, &config);
+            assert_eq!(view.status(), expected.status);
+            assert_eq!(view.edit_distance(), expected.editDistance);
+            assert_eq!(view.alphabet_length(), expected.alphabetLength);
+            assert_eq!(view.num_locations(), expected.numLocations);
+            assert_eq!(view.end_locations(), expected.end_locations());
+            assert_eq!(view.start_locations(), expected.start_locations());
+            assert_eq!(view.alignment(), expected.alignment());
+            assert_eq!(view.to_result().alignment(), expected.alignment());
+        }
+        // once grown, buffers are reused without reallocation
+        align_with_scratch(pairs[1].0, pairs[1].1, &config, &mut scratch);
+        let path_ptr = scratch.path.as_ptr();
+        let ends_ptr = scratch.ends.as_ptr();
+        for _ in 0..100 {
+            for &(query, target) in pairs.iter() {
+                align_with_scratch(query, target, &config, &mut scratch);
+            }
+        }
+        assert_eq!(scratch.path.as_ptr(), path_ptr);
+        assert_eq!(scratch.ends.as_ptr(), ends_ptr);
+        // buffers are kept when the result has no path
+        config.task = EdlibAlignTaskRs::EDLIB_TASK_DISTANCE;
+        let view = align_with_scratch(pairs[0].0, pairs[0].1, &config, &mut scratch);
+        assert!(view.alignment().is_none());
+        assert!(view.start_locations().is_none());
+        assert_eq!(view.edit_distance(), 2);
+        assert_eq!(scratch.path.as_ptr(), path_ptr);
+    } // end of test_align_with_scratch
+} // mod tests